@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::error::GruntError;
+use crate::types::{Axis, BordersParams, BorderType, CovarianceModel, FillValues, FillType, Params3D};
+use crate::model3d::{generate_dataset_with, Model3D};
+use crate::model3d::export::ExportOptions;
+
+pub const USAGE: &str = "\
+Usage: grunt <command> [options]
+
+Commands:
+    generate            Generate models from config file or flags
+    export <model>      Convert model (.json or .grunt) to another format
+    validate <model>    Check that model matches its params
+    info <model>        Print summary of model
+
+Generate options:
+    --config <file>         Params3D json config, flags below are ignored if it is set
+    --axis-x <s,e[,step]>   X axis generated on edges (default 0,100)
+    --axis-y <s,e[,step]>   Y axis generated on edges (default 0,100)
+    --axis-z <s,e[,step]>   Optional Z axis generated on edges
+    --borders <n>           Number of borders (default 2)
+    --limits <a:b,...>      Limits of borders (default 5:10,15:20)
+    --border-type <type>    random, step:<step>:<prob>, noise:<octaves>:<persistence>:<wavelength> or
+                            gaussian:<model>:<range>:<sill>:<nugget>, model - spherical, exponential or gaussian
+    --fill <min:max>        Fill every layer with random values between min and max (default 1:10)
+    --seed <n>              Master seed of dataset, every model gets its own seed (random by default)
+    --count <n>             Number of models (default 1)
+
+Common options:
+    --format <format>       json, binary, compressed, vtk, asc or xyz (default json)
+    --out-dir <dir>         Directory for output files (default .)
+    --name <name>           Base name of output files (default model_3d or name of input model)
+
+Export never overwrites input model, set another --out-dir, --name or --format for it";
+
+// Possible errors of command line arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    MissingModel,
+    OverwriteInput(String),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "Command is not set\n\n{USAGE}"),
+            CliError::UnknownCommand(command) => write!(f, "Unknown command {command}\n\n{USAGE}"),
+            CliError::UnknownFlag(flag) => write!(f, "Unknown option {flag} for this command"),
+            CliError::MissingValue(flag) => write!(f, "Option {flag} requires value"),
+            CliError::InvalidValue { flag, value } => write!(f, "Invalid value {value} of option {flag}"),
+            CliError::MissingModel => write!(f, "Path to model is not set"),
+            CliError::OverwriteInput(path) =>
+                write!(f, "Export would overwrite input model {path}, set another --out-dir, --name or --format"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+const GENERATE_FLAGS: [&str; 13] = ["--config", "--axis-x", "--axis-y", "--axis-z", "--borders", "--limits",
+    "--border-type", "--fill", "--seed", "--count", "--format", "--out-dir", "--name"];
+const EXPORT_FLAGS: [&str; 3] = ["--format", "--out-dir", "--name"];
+const FORMATS: [&str; 6] = ["json", "binary", "compressed", "vtk", "asc", "xyz"];
+
+// Positional arguments and flags with values of one command
+#[derive(Debug, Default)]
+struct Args {
+    positional: Vec<String>,
+    flags: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(args: &[String], allowed_flags: &[&str]) -> Result<Args, CliError> {
+        let mut result = Args::default();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                result.positional.push(arg.clone());
+                continue
+            }
+
+            if !allowed_flags.contains(&arg.as_str()) {
+                return Err(CliError::UnknownFlag(arg.clone()))
+            }
+            let value = args.next().ok_or_else(|| CliError::MissingValue(arg.clone()))?;
+            result.flags.insert(arg.clone(), value.clone());
+        }
+
+        Ok(result)
+    }
+
+    fn flag(&self, flag: &str) -> Option<&str> {
+        self.flags.get(flag).map(|value| value.as_str())
+    }
+
+    // Function to parse value of flag or return default if flag is not set
+    fn parsed_flag<T, F>(&self, flag: &str, default: T, parse: F) -> Result<T, CliError>
+    where
+        F: Fn(&str) -> Option<T>,
+    {
+        match self.flag(flag) {
+            Some(value) => parse(value).ok_or_else(|| CliError::InvalidValue {
+                flag: flag.to_string(),
+                value: value.to_string(),
+            }),
+            None => Ok(default),
+        }
+    }
+
+    fn model_path(&self) -> Result<&str, CliError> {
+        self.positional.first().map(|path| path.as_str()).ok_or(CliError::MissingModel)
+    }
+}
+
+/// Runs command from command line arguments (without name of program)
+pub fn run(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (command, args) = args.split_first().ok_or(CliError::MissingCommand)?;
+
+    match command.as_str() {
+        "generate" => generate(&Args::parse(args, &GENERATE_FLAGS)?),
+        "export" => export(&Args::parse(args, &EXPORT_FLAGS)?),
+        "validate" => validate(&Args::parse(args, &[])?),
+        "info" => info(&Args::parse(args, &[])?),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Ok(())
+        },
+        _ => Err(Box::new(CliError::UnknownCommand(command.clone()))),
+    }
+}
+
+fn generate(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let params = match args.flag("--config") {
+        Some(config) => Params3D::from_file(config)?,
+        None => params_from_flags(args)?,
+    };
+
+    let count: usize = args.parsed_flag("--count", 1, |value| value.parse().ok())?;
+    let seed: u64 = args.parsed_flag("--seed", rand::random(), |value| value.parse().ok())?;
+    let name = args.flag("--name").unwrap_or("model_3d");
+    let format = args.flag("--format").unwrap_or("json");
+    if !FORMATS.contains(&format) {
+        return Err(Box::new(CliError::InvalidValue { flag: String::from("--format"), value: format.to_string() }))
+    }
+
+    generate_dataset_with(params, count, seed, |model_num, model, done| {
+        let path = output_path(args, &format!("{name}_{model_num}"))?;
+        write_model(&model, &path, format).map_err(|err| std::io::Error::other(err.to_string()))?;
+        if count > 1 {
+            eprintln!("Generated {done}/{count}");
+        }
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+fn export(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let model_path = args.model_path()?;
+    let model = read_model(model_path)?;
+
+    let name = match args.flag("--name") {
+        Some(name) => name.to_string(),
+        None => Path::new(model_path).file_stem().and_then(|stem| stem.to_str()).unwrap_or("model_3d").to_string(),
+    };
+
+    let path = output_path(args, &name)?;
+    let format = args.flag("--format").unwrap_or("json");
+    let input = std::fs::canonicalize(model_path)?;
+    if output_files(&model, &path, format).iter().any(|file| std::fs::canonicalize(file).is_ok_and(|file| file == input)) {
+        return Err(Box::new(CliError::OverwriteInput(model_path.to_string())))
+    }
+
+    write_model(&model, &path, format)
+}
+
+fn validate(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let model_path = args.model_path()?;
+    read_model(model_path)?.validate()?;
+    println!("{model_path}: model is valid");
+    Ok(())
+}
+
+fn info(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let model_path = args.model_path()?;
+    println!("{}", model_info(&read_model(model_path)?));
+    Ok(())
+}
+
+// Function to build params from generate flags, every part is validated by its constructor
+fn params_from_flags(args: &Args) -> Result<Arc<Params3D>, Box<dyn std::error::Error>> {
+    let axis_x = Arc::new(args.parsed_flag("--axis-x", Axis::generate_axis_on_edges(0, 100, None)?, parse_axis)?);
+    let axis_y = Arc::new(args.parsed_flag("--axis-y", Axis::generate_axis_on_edges(0, 100, None)?, parse_axis)?);
+    let axis_z = args.parsed_flag("--axis-z", None, |value| parse_axis(value).map(Some))?.map(Arc::new);
+
+    let number_of_borders: u8 = args.parsed_flag("--borders", 2, |value| value.parse().ok())?;
+    let borders_limits = args.parsed_flag("--limits", vec![[5, 10], [15, 20]], parse_limits)?;
+    let border_type = args.parsed_flag("--border-type", BorderType::Random, parse_border_type)?;
+    let borders = Arc::new(BordersParams::new(number_of_borders, &[border_type], &borders_limits)?);
+
+    let fill_type = args.parsed_flag("--fill", None, |value| {
+        let (min, max) = value.split_once(':')?;
+        Some(Some(FillType::RandomBetween(min.trim().parse().ok()?, max.trim().parse().ok()?)))
+    })?;
+    let fill_values = match fill_type {
+        Some(fill_type) => FillValues::new(vec![fill_type], 0, true)?,
+        None => FillValues::default(),
+    };
+
+    Ok(Params3D::new(axis_x, axis_y, axis_z, borders, Arc::new(vec![fill_values])))
+}
+
+// Function to parse axis as start,end[,step]
+fn parse_axis(value: &str) -> Option<Axis> {
+    let values: Vec<f64> = value.split(',').map(|value| value.trim().parse().ok()).collect::<Option<_>>()?;
+    match values[..] {
+        [start, end] => Axis::generate_axis_on_edges(start, end, None).ok(),
+        [start, end, step] => Axis::generate_axis_on_edges(start, end, Some(step)).ok(),
+        _ => None,
+    }
+}
+
+// Function to parse limits as min:max,min:max,...
+fn parse_limits(value: &str) -> Option<Vec<[u32; 2]>> {
+    value.split(',').map(|limits| {
+        let (min, max) = limits.split_once(':')?;
+        Some([min.trim().parse().ok()?, max.trim().parse().ok()?])
+    }).collect()
+}
+
+// Function to parse border type as random, step:<step>:<prob>, noise:<octaves>:<persistence>:<wavelength>
+// or gaussian:<model>:<range>:<sill>:<nugget>
+fn parse_border_type(value: &str) -> Option<BorderType> {
+    let parts: Vec<&str> = value.split(':').collect();
+    match parts[..] {
+        ["random"] => Some(BorderType::Random),
+        ["step", step, prob] => Some(BorderType::RandomWithStep(step.parse().ok()?, prob.parse().ok()?)),
+        ["noise", octaves, persistence, wavelength] => Some(BorderType::Noise {
+            octaves: octaves.parse().ok()?,
+            persistence: persistence.parse().ok()?,
+            wavelength: wavelength.parse().ok()?,
+        }),
+        ["gaussian", model, range, sill, nugget] => Some(BorderType::GaussianField {
+            model: match model {
+                "spherical" => CovarianceModel::Spherical,
+                "exponential" => CovarianceModel::Exponential,
+                "gaussian" => CovarianceModel::Gaussian,
+                _ => return None,
+            },
+            range: range.parse().ok()?,
+            sill: sill.parse().ok()?,
+            nugget: nugget.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+// Function to get path of output file without extension, output directory is created if needed
+fn output_path(args: &Args, name: &str) -> Result<PathBuf, std::io::Error> {
+    let out_dir = PathBuf::from(args.flag("--out-dir").unwrap_or("."));
+    std::fs::create_dir_all(&out_dir)?;
+    Ok(out_dir.join(name))
+}
+
+// Function to get paths of files which write_model writes for format
+fn output_files(model: &Model3D, path: &Path, format: &str) -> Vec<PathBuf> {
+    let with_suffix = |suffix: &str| {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        PathBuf::from(file)
+    };
+
+    match format {
+        "json" => vec![with_suffix(".json")],
+        "binary" | "compressed" => vec![with_suffix(".grunt")],
+        "vtk" => vec![with_suffix(".vtk")],
+        "asc" | "xyz" => (0..model.borders().borders_count()).map(|border_num| {
+            with_suffix(&format!("_bo{border_num}.{format}"))
+        }).collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Function to read model from binary (.grunt) or json file
+fn read_model(path: &str) -> Result<Model3D, GruntError> {
+    match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+        Some("grunt") => Model3D::import_binary(path),
+        _ => Model3D::import_model(path),
+    }
+}
+
+// Function to write model in format, extension is added by exporters
+fn write_model(model: &Model3D, path: &Path, format: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let name = path.to_str().ok_or_else(|| CliError::InvalidValue {
+        flag: String::from("--out-dir"),
+        value: path.display().to_string(),
+    })?;
+
+    match format {
+        "json" => {
+            let options = ExportOptions::new().with_params().with_borders().with_fill_values().with_metadata();
+            model.export_model(name, &options)?
+        },
+        "binary" => model.export_binary(name, false)?,
+        "compressed" => model.export_binary(name, true)?,
+        "vtk" => model.export_vtk(name)?,
+        "asc" => model.export_asc(name)?,
+        "xyz" => model.export_xyz(name)?,
+        _ => return Err(Box::new(CliError::InvalidValue { flag: String::from("--format"), value: format.to_string() })),
+    }
+
+    Ok(())
+}
+
+/// Returns summary of model: seed, axes, borders and fill values
+pub fn model_info(model: &Model3D) -> String {
+    let params = model.params();
+    let axis_info = |axis: &Axis| {
+        let step = axis.step().map_or(String::from("non-uniform"), |step| format!("step {step}"));
+        format!("{} blocks from {} to {}, {step}", axis.blocks_count(), axis.start(), axis.end())
+    };
+
+    let mut info = format!("Seed: {}\n", model.seed().map_or(String::from("none"), |seed| seed.to_string()));
+    info += format!("Axis X: {}\n", axis_info(&params.axis_x())).as_str();
+    info += format!("Axis Y: {}\n", axis_info(&params.axis_y())).as_str();
+    info += format!("Axis Z: {}\n", params.axis_z().map_or(String::from("none"), |axis| axis_info(&axis))).as_str();
+    info += format!("Faults: {}\n", model.faults().len()).as_str();
+
+    info += format!("Borders: {}", model.borders().borders_count()).as_str();
+    for (border_num, border) in model.borders().borders().enumerate() {
+        let values = border.iter();
+        let min = values.clone().min().copied().unwrap_or(0);
+        let max = values.clone().max().copied().unwrap_or(0);
+        let mean = values.clone().map(|value| *value as f64).sum::<f64>() / values.count().max(1) as f64;
+        info += format!("\n    border {border_num}: min {min}, max {max}, mean {mean:.2}").as_str();
+    }
+
+    info += format!("\nFill values layers: {}", model.fill_values().len()).as_str();
+    info
+}
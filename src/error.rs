@@ -0,0 +1,97 @@
+use std::io;
+
+use nanoserde::DeJsonErr;
+
+use crate::model3d::{BorderValidationError, ModelImportError};
+use crate::types::{AxisError, BorderError, FaultError, FillValuesError};
+
+/// Any error of grunt, every variant keeps original error
+#[derive(Debug)]
+pub enum GruntError {
+    Axis(AxisError),
+    Borders(BorderError),
+    FillValues(FillValuesError),
+    Fault(FaultError),
+    Validation(BorderValidationError),
+    Import(ModelImportError),
+    Json(DeJsonErr),
+    Io(io::Error),
+}
+
+impl std::fmt::Display for GruntError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GruntError::Axis(err) => write!(f, "Axis: {err}"),
+            GruntError::Borders(err) => write!(f, "BordersParams: {err}"),
+            GruntError::FillValues(err) => write!(f, "FillValues: {err}"),
+            GruntError::Fault(err) => write!(f, "Fault: {err}"),
+            GruntError::Validation(err) => write!(f, "Invalid border: {err}"),
+            GruntError::Import(err) => write!(f, "Import: {err}"),
+            GruntError::Json(err) => write!(f, "Json: {err}"),
+            GruntError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for GruntError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GruntError::Axis(err) => Some(err),
+            GruntError::Borders(err) => Some(err),
+            GruntError::FillValues(err) => Some(err),
+            GruntError::Fault(err) => Some(err),
+            GruntError::Validation(err) => Some(err),
+            GruntError::Import(err) => Some(err),
+            GruntError::Json(err) => Some(err),
+            GruntError::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<AxisError> for GruntError {
+    fn from(err: AxisError) -> GruntError {
+        GruntError::Axis(err)
+    }
+}
+
+impl From<BorderError> for GruntError {
+    fn from(err: BorderError) -> GruntError {
+        GruntError::Borders(err)
+    }
+}
+
+impl From<FillValuesError> for GruntError {
+    fn from(err: FillValuesError) -> GruntError {
+        GruntError::FillValues(err)
+    }
+}
+
+impl From<FaultError> for GruntError {
+    fn from(err: FaultError) -> GruntError {
+        GruntError::Fault(err)
+    }
+}
+
+impl From<BorderValidationError> for GruntError {
+    fn from(err: BorderValidationError) -> GruntError {
+        GruntError::Validation(err)
+    }
+}
+
+impl From<ModelImportError> for GruntError {
+    fn from(err: ModelImportError) -> GruntError {
+        GruntError::Import(err)
+    }
+}
+
+impl From<DeJsonErr> for GruntError {
+    fn from(err: DeJsonErr) -> GruntError {
+        GruntError::Json(err)
+    }
+}
+
+impl From<io::Error> for GruntError {
+    fn from(err: io::Error) -> GruntError {
+        GruntError::Io(err)
+    }
+}
@@ -1,11 +1,18 @@
-mod types;
+pub mod types;
 mod model3d;
+mod error;
 
-pub use types::{Axis, AxisError};
-pub use types::{BordersParams, BorderType};
-pub use types::{FillValues, FillType};
+pub use types::{Axis, AxisError, AXIS_DECIMALS, FIXED_SCALE};
+pub use types::{BordersParams, BorderError, BorderType, BordersOrder, CovarianceModel};
+pub use types::{FillValues, FillValuesError, FillType};
+
+pub use types::{Fault, FaultError};
 
 pub use types::Params3D;
 
-pub use model3d::Model3D;
-pub use model3d::generate_model3d;
+pub use model3d::{Model3D, BorderGrid, BorderValidationError, ModelImportError, validate_layer};
+pub use model3d::{generate_model3d, generate_model3d_seeded, Interpolation};
+pub use model3d::{dataset_seed, generate_dataset, generate_dataset_with, DatasetError};
+pub use model3d::export::ExportOptions;
+
+pub use error::GruntError;
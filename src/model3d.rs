@@ -1,24 +1,88 @@
 use std::sync::Arc;
 
 use nanoserde::SerJson;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
-use crate::types::Params3D;
+use crate::types::{Axis, Fault, Params3D};
 
+mod border_grid;
+pub use border_grid::BorderGrid;
 mod borders3d;
+pub use borders3d::{BorderValidationError, validate_layer};
+mod fill_values3d;
+mod voxels;
+mod faults;
 pub mod export;
+mod import;
+mod binary;
+mod gis;
+mod vtk;
+mod dataset;
+pub use dataset::{dataset_seed, generate_dataset, generate_dataset_with, DatasetError};
+mod resample;
+pub use resample::Interpolation;
+pub use import::ModelImportError;
 
-pub fn generate_model3d(params: Arc<Params3D>) -> Model3D {
-    let borders = borders3d::generate_borders(params.clone());
-    Model3D {
+// Random streams for model parts, every border and layer takes stream = part stream + its id
+const BORDERS_STREAM: u64 = 0;
+const FILL_VALUES_STREAM: u64 = 1 << 32;
+const DATASET_STREAM: u64 = 2 << 32;
+
+/// Generates model with random master seed, use generate_model3d_seeded to reproduce model
+pub fn generate_model3d(params: Arc<Params3D>) -> Result<Model3D, BorderValidationError> {
+    generate_model3d_seeded(params, rand::random())
+}
+
+/// Generates model based on master seed, same params and seed always give same model.
+/// Every border and layer gets its own random stream, so adding new border doesn't change
+/// borders before it.
+/// Faults from params are applied to borders after generation.
+/// Returns error if any generated border is invalid
+pub fn generate_model3d_seeded(params: Arc<Params3D>, seed: u64) -> Result<Model3D, BorderValidationError> {
+    generate_model3d_threads(params, seed, available_threads())
+}
+
+// Function to generate model with at most threads_count threads for borders, result doesn't
+// depend on number of threads
+fn generate_model3d_threads(params: Arc<Params3D>, seed: u64, threads_count: usize) -> Result<Model3D, BorderValidationError> {
+    let mut borders = borders3d::generate_borders(params.clone(), seed, threads_count)?;
+    faults::apply_faults(&params, &mut borders)?;
+    let fill_values = fill_values3d::generate_fill_values(params.clone(), seed);
+    Ok(Model3D {
         params,
+        seed: Some(seed),
         borders,
+        fill_values,
+    })
+}
+
+// Function to get number of threads which can run in parallel
+fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+// Function to create deterministic random generator for one part of model
+fn part_rng(seed: u64, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+// Function to convert border value to Z coordinate, border with value n lies on n edge of axis_z
+pub(crate) fn border_z(value: u32, axis_z: Option<&Axis>) -> f64 {
+    match axis_z {
+        Some(axis_z) => axis_z.edge((value as usize).min(axis_z.blocks_count())),
+        None => value as f64,
     }
 }
 
 #[derive(Clone, Debug, SerJson)]
 pub struct Model3D {
     params: Arc<Params3D>,
-    borders: Vec<Vec<Vec<u32>>>,
+    seed: Option<u64>,
+    borders: BorderGrid,
+    fill_values: Vec<Vec<Vec<f32>>>,
 }
 
 impl Model3D {
@@ -26,7 +90,29 @@ impl Model3D {
         self.params.clone()
     }
 
-    pub fn borders(&self) -> &Vec<Vec<Vec<u32>>> {
+    /// Returns master seed used to generate model, imported models have no seed
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns faults applied to borders
+    pub fn faults(&self) -> Arc<Vec<Fault>> {
+        self.params.faults()
+    }
+
+    /// Returns borders as border_num -> y -> x
+    pub fn borders(&self) -> &BorderGrid {
         &self.borders
     }
+
+    /// Returns fill values for every layer as layer_num -> y -> x
+    /// Layer 0 is above border 0, layer n is below border n - 1
+    pub fn fill_values(&self) -> &Vec<Vec<Vec<f32>>> {
+        &self.fill_values
+    }
+
+    /// Returns fill value of layer in block (y, x) or None if any index is out of model
+    pub fn fill_value(&self, layer: usize, y: usize, x: usize) -> Option<f32> {
+        self.fill_values.get(layer)?.get(y)?.get(x).copied()
+    }
 }
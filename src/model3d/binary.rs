@@ -0,0 +1,242 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use nanoserde::{DeJson, SerJson};
+
+use crate::error::GruntError;
+use crate::model3d::Model3D;
+use crate::model3d::BorderGrid;
+use crate::model3d::import::ModelImportError;
+use crate::types::Params3D;
+
+// Binary format: magic, version and flags are never compressed, everything after them can be
+const BINARY_MAGIC: &[u8; 4] = b"GRNT";
+const BINARY_VERSION: u16 = 1;
+const COMPRESSED_FLAG: u16 = 1;
+
+impl Model3D {
+    /// Exports model to compact binary file {name}.grunt, see write_binary for format
+    pub fn export_binary(&self, name: &str, compress: bool) -> Result<(), io::Error> {
+        let file = BufWriter::new(File::create(format!("{name}.grunt"))?);
+        self.write_binary(file, compress)
+    }
+
+    /// Writes model in binary format, every number is little-endian:
+    /// 1. Header: b"GRNT", version (u16), flags (u16, first bit - compression)
+    /// 2. Params as json: length (u32) and bytes
+    /// 3. Seed: 1 (u8) and seed (u64) or only 0 (u8) if model has no seed
+    /// 4. Borders: count, y size, x size (all u32) and values (u32) as border_num -> y -> x
+    /// 5. Fill values: count, y size, x size (all u32) and values (f32) as layer_num -> y -> x
+    ///
+    /// Everything after header is compressed with zlib if compress is true, compression
+    /// requires "compression" feature
+    pub fn write_binary<W: Write>(&self, mut writer: W, compress: bool) -> Result<(), io::Error> {
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&BINARY_VERSION.to_le_bytes())?;
+        writer.write_all(&(if compress { COMPRESSED_FLAG } else { 0 }).to_le_bytes())?;
+
+        if compress {
+            return write_compressed(writer, self)
+        }
+
+        write_body(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Imports model from binary file written by export_binary or write_binary
+    pub fn import_binary<P: AsRef<Path>>(path: P) -> Result<Model3D, GruntError> {
+        Model3D::read_binary(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads model in binary format, borders and fill values sizes are checked with params before
+    /// reading values, so malformed file can't cause huge allocations
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Model3D, GruntError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != BINARY_MAGIC {
+            return Err(invalid_data("Not a grunt binary model").into())
+        }
+
+        if read_u16(&mut reader)? != BINARY_VERSION {
+            return Err(invalid_data("Unsupported version of grunt binary model").into())
+        }
+
+        if read_u16(&mut reader)? & COMPRESSED_FLAG != 0 {
+            read_body(&mut read_compressed(reader)?)
+        } else {
+            read_body(&mut reader)
+        }
+    }
+}
+
+fn write_body<W: Write>(writer: &mut W, model: &Model3D) -> Result<(), io::Error> {
+    let params = model.params.serialize_json();
+    writer.write_all(&(params.len() as u32).to_le_bytes())?;
+    writer.write_all(params.as_bytes())?;
+
+    match model.seed {
+        Some(seed) => {
+            writer.write_all(&[1])?;
+            writer.write_all(&seed.to_le_bytes())?;
+        },
+        None => writer.write_all(&[0])?,
+    }
+
+    let borders_rows = model.borders.as_slice().chunks_exact(model.borders.x_size().max(1));
+    let (borders_count, y_size, x_size) = model.borders.shape();
+    write_grid(writer, [borders_count, y_size, x_size], borders_rows, |value| value.to_le_bytes())?;
+
+    let fill_values = &model.fill_values;
+    let y_size = fill_values.first().map_or(0, |layer| layer.len());
+    let x_size = fill_values.first().and_then(|layer| layer.first()).map_or(0, |row| row.len());
+    let fill_values_rows = fill_values.iter().flatten().map(|row| row.as_slice());
+    write_grid(writer, [fill_values.len(), y_size, x_size], fill_values_rows, |value| value.to_le_bytes())
+}
+
+fn read_body<R: Read>(reader: &mut R) -> Result<Model3D, GruntError> {
+    // Params are read up to their length, so wrong length can't allocate more than file contains
+    let params_len = read_u32(reader)? as usize;
+    let mut params: Vec<u8> = Vec::new();
+    reader.by_ref().take(params_len as u64).read_to_end(&mut params)?;
+    if params.len() != params_len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+    }
+    let params = String::from_utf8(params).map_err(|_| invalid_data("Params of grunt binary model aren't utf-8"))?;
+    let params = Params3D::deserialize_json(&params)?;
+
+    let mut has_seed = [0u8; 1];
+    reader.read_exact(&mut has_seed)?;
+    let seed = match has_seed[0] {
+        0 => None,
+        _ => Some(u64::from_le_bytes(read_array(reader)?)),
+    };
+
+    let axes_sizes = (params.axis_y().blocks_count(), params.axis_x().blocks_count());
+    let number_of_borders = params.borders().number_of_borders() as usize;
+
+    // Model without borders or fill values has zero count and either zero or axes sizes
+    let [borders_count, y_size, x_size] = read_sizes(reader)?;
+    if borders_count != 0 || (y_size, x_size) != (0, 0) {
+        if borders_count != 0 && borders_count != number_of_borders {
+            return Err(ModelImportError::BordersCount { expected: number_of_borders, found: borders_count }.into())
+        }
+        if y_size != axes_sizes.0 {
+            return Err(ModelImportError::RowsCount { border_id: 0, expected: axes_sizes.0, found: y_size }.into())
+        }
+        if x_size != axes_sizes.1 {
+            return Err(ModelImportError::RowLength { border_id: 0, y: 0, expected: axes_sizes.1, found: x_size }.into())
+        }
+    }
+    let borders = read_grid(reader, [borders_count, y_size, x_size], u32::from_le_bytes)?;
+    let borders = BorderGrid::from_vec(borders_count, y_size, x_size, borders)
+        .ok_or_else(|| invalid_data("Borders of grunt binary model don't match their sizes"))?;
+
+    let [layers_count, y_size, x_size] = read_sizes(reader)?;
+    let number_of_layers = number_of_borders + 1;
+    if layers_count != 0 || (y_size, x_size) != (0, 0) {
+        if layers_count != 0 && layers_count != number_of_layers {
+            return Err(ModelImportError::FillValuesSize { layer_id: layers_count.min(number_of_layers) }.into())
+        }
+        if (y_size, x_size) != axes_sizes {
+            return Err(ModelImportError::FillValuesSize { layer_id: 0 }.into())
+        }
+    }
+    let fill_values = read_grid(reader, [layers_count, y_size, x_size], f32::from_le_bytes)?;
+    let fill_values: Vec<Vec<Vec<f32>>> = fill_values.chunks_exact((y_size * x_size).max(1)).map(|layer| {
+        layer.chunks_exact(x_size).map(|row| row.to_vec()).collect()
+    }).collect();
+
+    Ok(Model3D {
+        params: Arc::new(params),
+        seed,
+        borders,
+        fill_values,
+    })
+}
+
+// Function to write 3D grid as sizes and values, every row must have x size values
+fn write_grid<'a, W: Write, T: Copy + 'a>(
+    writer: &mut W,
+    sizes: [usize; 3],
+    rows: impl Iterator<Item = &'a [T]>,
+    to_bytes: fn(T) -> [u8; 4],
+) -> Result<(), io::Error> {
+    for size in sizes {
+        writer.write_all(&(size as u32).to_le_bytes())?;
+    }
+
+    let mut row_bytes: Vec<u8> = Vec::with_capacity(sizes[2] * 4);
+    for row in rows {
+        row_bytes.clear();
+        row_bytes.extend(row.iter().flat_map(|value| to_bytes(*value)));
+        writer.write_all(&row_bytes)?;
+    }
+
+    Ok(())
+}
+
+// Function to read sizes of 3D grid: count, y size and x size
+fn read_sizes<R: Read>(reader: &mut R) -> Result<[usize; 3], io::Error> {
+    Ok([read_u32(reader)? as usize, read_u32(reader)? as usize, read_u32(reader)? as usize])
+}
+
+// Function to read values of 3D grid stored one by one, sizes must be checked before
+fn read_grid<R: Read, T>(reader: &mut R, sizes: [usize; 3], from_bytes: fn([u8; 4]) -> T) -> Result<Vec<T>, io::Error> {
+    let [count, y_size, x_size] = sizes;
+    let too_big = || invalid_data("Grid of grunt binary model is too big");
+    let rows_count = count.checked_mul(y_size).ok_or_else(too_big)?;
+    let values_count = rows_count.checked_mul(x_size).ok_or_else(too_big)?;
+    let row_len = x_size.checked_mul(4).ok_or_else(too_big)?;
+
+    // Row buffer is allocated only if grid has rows, so sizes of empty grid don't matter
+    let mut row_bytes = if rows_count > 0 { vec![0u8; row_len] } else { Vec::new() };
+    let mut grid: Vec<T> = Vec::with_capacity(values_count.min(1 << 24));
+    for _ in 0..rows_count {
+        reader.read_exact(&mut row_bytes)?;
+        grid.extend(row_bytes.chunks_exact(4).map(|bytes| from_bytes(bytes.try_into().unwrap())));
+    }
+
+    Ok(grid)
+}
+
+#[cfg(feature = "compression")]
+fn write_compressed<W: Write>(writer: W, model: &Model3D) -> Result<(), io::Error> {
+    let mut encoder = flate2::write::ZlibEncoder::new(writer, flate2::Compression::default());
+    write_body(&mut encoder, model)?;
+    encoder.finish()?.flush()
+}
+
+#[cfg(not(feature = "compression"))]
+fn write_compressed<W: Write>(_writer: W, _model: &Model3D) -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "Compression requires \"compression\" feature"))
+}
+
+#[cfg(feature = "compression")]
+fn read_compressed<R: Read>(reader: R) -> Result<impl Read, io::Error> {
+    Ok(flate2::read::ZlibDecoder::new(reader))
+}
+
+#[cfg(not(feature = "compression"))]
+fn read_compressed<R: Read>(_reader: R) -> Result<R, io::Error> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "Compressed model requires \"compression\" feature"))
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], io::Error> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> Result<u16, io::Error> {
+    Ok(u16::from_le_bytes(read_array(reader)?))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, io::Error> {
+    Ok(u32::from_le_bytes(read_array(reader)?))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
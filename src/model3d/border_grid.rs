@@ -0,0 +1,210 @@
+use std::ops::{Index, IndexMut};
+
+use nanoserde::SerJson;
+
+/// Borders of model stored in one contiguous buffer as border -> y -> x
+/// # Examples
+/// ```
+/// use grunt::BorderGrid;
+/// let mut grid = BorderGrid::new(2, 3, 4);
+/// grid[(1, 2, 3)] = 7;
+/// assert_eq!(grid.row(1, 2), &[0, 0, 0, 7]);
+/// assert_eq!(grid.border(1).len(), 12);
+/// assert_eq!(grid.get(2, 0, 0), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, SerJson)]
+pub struct BorderGrid {
+    borders_count: usize,
+    y_size: usize,
+    x_size: usize,
+    values: Vec<u32>,
+}
+
+impl BorderGrid {
+    /// Creates grid filled with zeros
+    pub fn new(borders_count: usize, y_size: usize, x_size: usize) -> BorderGrid {
+        BorderGrid {
+            borders_count,
+            y_size,
+            x_size,
+            values: vec![0; borders_count * y_size * x_size],
+        }
+    }
+
+    /// Creates grid from values stored as border -> y -> x, returns None if number of values
+    /// doesn't match sizes
+    pub fn from_vec(borders_count: usize, y_size: usize, x_size: usize, values: Vec<u32>) -> Option<BorderGrid> {
+        if values.len() != borders_count * y_size * x_size {
+            return None
+        }
+
+        Some(BorderGrid { borders_count, y_size, x_size, values })
+    }
+
+    /// Creates grid from nested borders, returns None if borders or rows have different sizes
+    /// # Examples
+    /// ```
+    /// use grunt::BorderGrid;
+    /// let grid = BorderGrid::from_nested(&[vec![vec![1, 2], vec![3, 4]]]).unwrap();
+    /// assert_eq!(grid.to_nested(), vec![vec![vec![1, 2], vec![3, 4]]]);
+    /// assert!(BorderGrid::from_nested(&[vec![vec![1, 2], vec![3]]]).is_none());
+    /// ```
+    pub fn from_nested(borders: &[Vec<Vec<u32>>]) -> Option<BorderGrid> {
+        let y_size = borders.first().map_or(0, |border| border.len());
+        let x_size = borders.first().and_then(|border| border.first()).map_or(0, |y_row| y_row.len());
+
+        let mut values: Vec<u32> = Vec::with_capacity(borders.len() * y_size * x_size);
+        for border in borders {
+            if border.len() != y_size {
+                return None
+            }
+            for y_row in border {
+                if y_row.len() != x_size {
+                    return None
+                }
+                values.extend_from_slice(y_row);
+            }
+        }
+
+        BorderGrid::from_vec(borders.len(), y_size, x_size, values)
+    }
+
+    /// Returns copy of grid as border -> y -> x nested vectors
+    pub fn to_nested(&self) -> Vec<Vec<Vec<u32>>> {
+        self.borders().map(|border| border.chunks_exact(self.x_size.max(1)).map(|y_row| y_row.to_vec()).collect())
+            .collect()
+    }
+}
+
+impl BorderGrid {
+    pub fn borders_count(&self) -> usize {
+        self.borders_count
+    }
+
+    pub fn y_size(&self) -> usize {
+        self.y_size
+    }
+
+    pub fn x_size(&self) -> usize {
+        self.x_size
+    }
+
+    /// Returns (borders count, y size, x size)
+    pub fn shape(&self) -> (usize, usize, usize) {
+        (self.borders_count, self.y_size, self.x_size)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.borders_count == 0
+    }
+
+    /// Returns value of border in block (y, x) or None if any index is out of grid
+    pub fn get(&self, border: usize, y: usize, x: usize) -> Option<u32> {
+        if border >= self.borders_count || y >= self.y_size || x >= self.x_size {
+            return None
+        }
+
+        Some(self.values[self.offset(border, y, x)])
+    }
+
+    /// Returns all values of border as y -> x
+    pub fn border(&self, border: usize) -> &[u32] {
+        let plane_size = self.plane_size();
+        &self.values[border * plane_size..(border + 1) * plane_size]
+    }
+
+    pub fn border_mut(&mut self, border: usize) -> &mut [u32] {
+        let plane_size = self.plane_size();
+        &mut self.values[border * plane_size..(border + 1) * plane_size]
+    }
+
+    /// Returns row y of border
+    pub fn row(&self, border: usize, y: usize) -> &[u32] {
+        let start = self.offset(border, y, 0);
+        &self.values[start..start + self.x_size]
+    }
+
+    pub fn row_mut(&mut self, border: usize, y: usize) -> &mut [u32] {
+        let start = self.offset(border, y, 0);
+        &mut self.values[start..start + self.x_size]
+    }
+
+    /// Iterates over borders, every border is y -> x slice
+    pub fn borders(&self) -> std::slice::ChunksExact<'_, u32> {
+        self.values.chunks_exact(self.plane_size().max(1))
+    }
+
+    pub fn borders_mut(&mut self) -> std::slice::ChunksExactMut<'_, u32> {
+        let plane_size = self.plane_size().max(1);
+        self.values.chunks_exact_mut(plane_size)
+    }
+
+    /// Iterates over rows of border
+    pub fn rows(&self, border: usize) -> std::slice::ChunksExact<'_, u32> {
+        self.border(border).chunks_exact(self.x_size.max(1))
+    }
+
+    /// Iterates over all values as border -> y -> x
+    pub fn iter(&self) -> std::slice::Iter<'_, u32> {
+        self.values.iter()
+    }
+
+    pub fn as_slice(&self) -> &[u32] {
+        &self.values
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u32] {
+        &mut self.values
+    }
+
+    pub fn into_vec(self) -> Vec<u32> {
+        self.values
+    }
+
+    fn plane_size(&self) -> usize {
+        self.y_size * self.x_size
+    }
+
+    fn offset(&self, border: usize, y: usize, x: usize) -> usize {
+        (border * self.y_size + y) * self.x_size + x
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl BorderGrid {
+    /// Returns zero-copy ndarray view with shape (borders count, y size, x size)
+    pub fn view(&self) -> ndarray::ArrayView3<'_, u32> {
+        ndarray::ArrayView3::from_shape(self.shape(), &self.values).unwrap()
+    }
+
+    pub fn view_mut(&mut self) -> ndarray::ArrayViewMut3<'_, u32> {
+        let shape = self.shape();
+        ndarray::ArrayViewMut3::from_shape(shape, &mut self.values).unwrap()
+    }
+}
+
+impl Index<(usize, usize, usize)> for BorderGrid {
+    type Output = u32;
+
+    fn index(&self, (border, y, x): (usize, usize, usize)) -> &u32 {
+        assert!(border < self.borders_count && y < self.y_size && x < self.x_size, "Index is out of border grid");
+        &self.values[self.offset(border, y, x)]
+    }
+}
+
+impl IndexMut<(usize, usize, usize)> for BorderGrid {
+    fn index_mut(&mut self, (border, y, x): (usize, usize, usize)) -> &mut u32 {
+        assert!(border < self.borders_count && y < self.y_size && x < self.x_size, "Index is out of border grid");
+        let offset = self.offset(border, y, x);
+        &mut self.values[offset]
+    }
+}
+
+impl<'a> IntoIterator for &'a BorderGrid {
+    type Item = &'a u32;
+    type IntoIter = std::slice::Iter<'a, u32>;
+
+    fn into_iter(self) -> std::slice::Iter<'a, u32> {
+        self.values.iter()
+    }
+}
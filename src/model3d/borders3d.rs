@@ -1,102 +1,276 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use crate::types::{BorderType, Params3D};
+use crate::types::{BorderType, BordersOrder, BordersParams, CovarianceModel, Params3D};
+use crate::model3d::{part_rng, BorderGrid, BORDERS_STREAM};
 
 mod random;
 mod random_with_step;
+mod noise;
+mod gaussian_field;
+
+// Possible violations found during borders validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BorderValidationError {
+    /// Border must have at least 2 blocks for both axes
+    GridTooSmall { layer_id: usize },
+    /// Number of values of border or fault offsets doesn't match axes sizes
+    SizeMismatch { layer_id: usize, expected: usize, found: usize },
+    /// Value is out of border's limits
+    OutOfLimits { layer_id: usize, y: usize, x: usize },
+    /// Difference between value and previous value (x - 1) is bigger than step
+    StepOverflowLeft { layer_id: usize, y: usize, x: usize },
+    /// Difference between value and upper value (y - 1) is bigger than step
+    StepOverflowUpper { layer_id: usize, y: usize, x: usize },
+    /// Value is closer to previous border than min thickness of ordered borders
+    OrderOverflow { layer_id: usize, y: usize, x: usize },
+    /// Circulant embedding of gaussian field for axes has too many cells
+    EmbeddingTooBig { layer_id: usize, size: usize },
+    /// Circulant embedding of gaussian field has negative eigenvalues for every allowed size
+    EmbeddingNotDefinite { layer_id: usize },
+}
+
+impl std::fmt::Display for BorderValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BorderValidationError::GridTooSmall { layer_id } =>
+                write!(f, "Border {layer_id}: cannot validate border because of small size"),
+            BorderValidationError::SizeMismatch { layer_id, expected, found } =>
+                write!(f, "Border {layer_id}: must contain {expected} values (Y size * X size), found {found}"),
+            BorderValidationError::OutOfLimits { layer_id, y, x } =>
+                write!(f, "Border {layer_id}, element y - {y}; x - {x}: out of limits bounds"),
+            BorderValidationError::StepOverflowLeft { layer_id, y, x } =>
+                write!(f, "Border {layer_id}, element y - {y}; x - {x}: step overflow with previous element"),
+            BorderValidationError::StepOverflowUpper { layer_id, y, x } =>
+                write!(f, "Border {layer_id}, element y - {y}; x - {x}: step overflow with upper element"),
+            BorderValidationError::OrderOverflow { layer_id, y, x } =>
+                write!(f, "Border {layer_id}, element y - {y}; x - {x}: closer to previous border than min thickness"),
+            BorderValidationError::EmbeddingTooBig { layer_id, size } =>
+                write!(f, "Border {layer_id}: gaussian field needs {size} cells of embedding, use coarser axes"),
+            BorderValidationError::EmbeddingNotDefinite { layer_id } =>
+                write!(f, "Border {layer_id}: gaussian field embedding isn't positive definite, use shorter range"),
+        }
+    }
+}
+
+impl std::error::Error for BorderValidationError {}
+
+// Circulant embeddings of every distinct covariance (model, range, sill) of gaussian field borders
+type FieldEmbeddings = Vec<((CovarianceModel, f32, f32), Result<gaussian_field::Embedding, gaussian_field::EmbeddingError>)>;
 
 /// Function to generate borders data for model
-/// Borders are represented as border_num -> y -> x
-pub fn generate_borders(params: Arc<Params3D>) -> Vec<Vec<Vec<u32>>> {
-    let ax_y_size = params.axis_y().blocks_centers().len();
-    let ax_x_size = params.axis_x().blocks_centers().len();
+/// Borders are stored in BorderGrid as border_num -> y -> x
+/// Every border is generated with its own random stream based on seed, so free borders are
+/// generated in at most threads_count parallel threads and result doesn't depend on number of
+/// threads. If several borders are invalid, error of the first one is returned
+/// Ordered borders are generated one by one, every border is generated not higher than previous
+/// border plus min thickness, so it keeps its own limits and max step
+pub fn generate_borders(params: Arc<Params3D>, seed: u64, threads_count: usize) -> Result<BorderGrid, BorderValidationError> {
+    let number_of_borders = params.borders().number_of_borders() as usize;
+    let axes_size = (params.axis_y().blocks_count(), params.axis_x().blocks_count());
+    let mut borders = BorderGrid::new(number_of_borders, axes_size.0, axes_size.1);
+    let embeddings = field_embeddings(&params, number_of_borders);
 
-    let borders_params = params.borders();
-    let number_of_borders = borders_params.number_of_borders() as usize;
-    let border_types = borders_params.borders_type();
+    if let BordersOrder::Ordered(min_thickness) = params.borders().borders_order() {
+        let border_size = axes_size.0 * axes_size.1;
+        for now_border_id in 0..number_of_borders {
+            let (pr_borders, now_borders) = borders.as_mut_slice().split_at_mut(now_border_id * border_size);
+            let pr_border = pr_borders.len().checked_sub(border_size).map(|pr_start| (&pr_borders[pr_start..], min_thickness));
+            generate_border(&params, &embeddings, now_border_id, seed, &mut now_borders[..border_size], pr_border)?;
+        }
+        return Ok(borders)
+    }
 
-    let now_limits_count = borders_params.borders_limits().len();
-    let now_border_types_count = border_types.len();
-
-    let mut borders: Vec<Vec<Vec<u32>>> = Vec::with_capacity(params.borders().number_of_borders() as usize);
-
-    for now_border_id in 0..number_of_borders {
-        let now_limits = borders_params.borders_limits()[now_border_id % now_limits_count];
-        println!("{:?}", now_limits);
-        let axes_size = (ax_y_size, ax_x_size);
-
-        borders.push(
-            match border_types[now_border_id % now_border_types_count] {
-                BorderType::Random => {
-                    random::generate_layer(axes_size, now_limits)
-                },
-                BorderType::RandomWithStep(step, prob) => {
-                    random_with_step::generate_layer(step, prob, axes_size, now_limits)
-                },
-        });
-
-        //TODO: If validation needed
-        match border_types[now_border_id / number_of_borders] {
-            BorderType::Random => { validate_layer(&borders[borders.len() - 1], now_limits, None)},
-            BorderType::RandomWithStep(step, _prob) => {
-                validate_layer(&borders[borders.len() - 1], now_limits, Some(step));
-            }
+    let threads_count = threads_count.min(number_of_borders);
+    if threads_count <= 1 {
+        for (now_border_id, now_border) in borders.borders_mut().enumerate() {
+            generate_border(&params, &embeddings, now_border_id, seed, now_border, None)?;
         }
+        return Ok(borders)
     }
 
-    borders
+    let mut results: Vec<Result<(), BorderValidationError>> = vec![Ok(()); number_of_borders];
+
+    // Every thread takes next free border and fills its part of grid
+    let next_border = Mutex::new(borders.borders_mut().zip(results.iter_mut()).enumerate());
+    std::thread::scope(|scope| {
+        for _ in 0..threads_count {
+            scope.spawn(|| loop {
+                let Some((now_border_id, (now_border, now_result))) = next_border.lock().unwrap().next() else {
+                    break
+                };
+                *now_result = generate_border(&params, &embeddings, now_border_id, seed, now_border, None);
+            });
+        }
+    });
+
+    results.into_iter().collect::<Result<(), BorderValidationError>>()?;
+
+    Ok(borders)
 }
 
-//TODO: Rewrite to Result type and logging
-pub fn validate_layer(border_to_check: &[Vec<u32>], limits: [u32; 2], step: Option<u16>) {
-    // for i in border_to_check {
-    //     for j in i {
-    //         print!("{j}\t")
-    //     }
-    //     println!();
-    // }
-
-    if border_to_check.len() < 2 || border_to_check[0].len() < 2 {
-        panic!("Cannot validate layer because of small size");
+// Function to build circulant embeddings of gaussian field borders, they depend only on axes and
+// covariance, so every embedding is built once and shared by all borders with its covariance
+fn field_embeddings(params: &Params3D, number_of_borders: usize) -> FieldEmbeddings {
+    let (axis_y, axis_x) = (params.axis_y(), params.axis_x());
+    let centers = (axis_y.blocks_centers().as_slice(), axis_x.blocks_centers().as_slice());
+    let mut embeddings: FieldEmbeddings = Vec::new();
+
+    for border_type in params.borders().borders_type().iter().take(number_of_borders) {
+        let BorderType::GaussianField { model, range, sill, nugget: _ } = *border_type else {
+            continue
+        };
+        if embeddings.iter().any(|(covariance, _)| *covariance == (model, range, sill)) {
+            continue
+        }
+        let embedding = gaussian_field::embedding(model, range, sill, centers);
+        embeddings.push(((model, range, sill), embedding));
     }
 
-    let mut pr_el = border_to_check[0][0];
-    if pr_el < limits[0] || pr_el > limits[1] {
-        panic!("Element y - 0; x - 0: out of limits bounds");
+    embeddings
+}
+
+// Function to generate and validate one border with its own random stream
+// embeddings - circulant embeddings of gaussian field borders built by field_embeddings
+// pr_border - previous border and min thickness for ordered borders, every value of border is
+// generated not higher than previous value plus min thickness
+fn generate_border(
+    params: &Params3D,
+    embeddings: &FieldEmbeddings,
+    now_border_id: usize,
+    seed: u64,
+    now_border: &mut [u32],
+    pr_border: Option<(&[u32], u32)>,
+) -> Result<(), BorderValidationError> {
+    let axis_y = params.axis_y();
+    let axis_x = params.axis_x();
+    let axes_size = (axis_y.blocks_count(), axis_x.blocks_count());
+
+    let borders_params = params.borders();
+    let border_types = borders_params.borders_type();
+    let (now_limits, now_step) = border_rules(&borders_params, now_border_id);
+    let mut rng = part_rng(seed, BORDERS_STREAM + now_border_id as u64);
+
+    let lower_bounds: Option<Vec<u32>> = pr_border.map(|(pr_border, min_thickness)| {
+        pr_border.iter().map(|pr_value| pr_value.saturating_add(min_thickness).max(now_limits[0])).collect()
+    });
+    let lower_bounds = lower_bounds.as_deref();
+
+    match border_types[now_border_id % border_types.len()] {
+        BorderType::Random => {
+            random::generate_layer(now_limits, lower_bounds, now_border, &mut rng)
+        },
+        BorderType::RandomWithStep(step, prob) => {
+            random_with_step::generate_layer(step, prob, axes_size, now_limits, lower_bounds, now_border, &mut rng)
+        },
+        BorderType::Noise { octaves, persistence, wavelength } => {
+            let centers = (axis_y.blocks_centers().as_slice(), axis_x.blocks_centers().as_slice());
+            noise::generate_layer(octaves, persistence, wavelength, centers, now_limits, lower_bounds, now_border, &mut rng)
+        },
+        BorderType::GaussianField { model, range, sill, nugget } => {
+            // Embedding is built for every gaussian field border, so it always exists
+            let (_, embedding) = embeddings.iter().find(|(covariance, _)| *covariance == (model, range, sill)).unwrap();
+            let embedding = embedding.as_ref().map_err(|err| match *err {
+                gaussian_field::EmbeddingError::TooBig { size } =>
+                    BorderValidationError::EmbeddingTooBig { layer_id: now_border_id, size },
+                gaussian_field::EmbeddingError::NotDefinite =>
+                    BorderValidationError::EmbeddingNotDefinite { layer_id: now_border_id },
+            })?;
+
+            let centers = (axis_y.blocks_centers().as_slice(), axis_x.blocks_centers().as_slice());
+            gaussian_field::generate_layer(embedding, nugget, centers, now_limits, lower_bounds, now_border, &mut rng)
+        },
+    };
+
+    validate_layer(now_border_id, now_border, axes_size, now_limits, now_step, None)?;
+    match pr_border {
+        Some((pr_border, min_thickness)) => validate_order(now_border_id, now_border, pr_border, axes_size.1, min_thickness),
+        None => Ok(()),
     }
+}
 
-    for (now_id, now_el) in border_to_check[0].iter().enumerate().skip(1) {
-        if *now_el < limits[0] || *now_el > limits[1] {
-            panic!("Element y - 0; x - {now_id}: out of limits bounds");
-        }
+/// Function to get limits and max step (if border type has it) of border, every border x takes
+/// its params by module(x mod n.len)
+pub fn border_rules(borders_params: &BordersParams, border_id: usize) -> ([u32; 2], Option<u16>) {
+    let borders_limits = borders_params.borders_limits();
+    let borders_type = borders_params.borders_type();
+
+    let step = match borders_type[border_id % borders_type.len()] {
+        BorderType::RandomWithStep(step, _prob) => Some(step),
+        _ => None,
+    };
+
+    (borders_limits[border_id % borders_limits.len()], step)
+}
 
-        if step.is_some() && pr_el.abs_diff(*now_el) as u16 > step.unwrap() {
-            panic!("Element y - 0; x - {now_id}: step overflow");
+/// Function to check that every value of border is at least min thickness below value of previous
+/// border, x_size - size of X axis
+pub(super) fn validate_order(
+    layer_id: usize,
+    border: &[u32],
+    pr_border: &[u32],
+    x_size: usize,
+    min_thickness: u32,
+) -> Result<(), BorderValidationError> {
+    for (now_id, (now_value, pr_value)) in border.iter().zip(pr_border).enumerate() {
+        if *now_value < pr_value.saturating_add(min_thickness) {
+            return Err(BorderValidationError::OrderOverflow { layer_id, y: now_id / x_size, x: now_id % x_size })
         }
+    }
 
-        pr_el = *now_el;
+    Ok(())
+}
+
+/// Function to check that every value of border is inside limits and, if step exists, that
+/// difference between every value and its previous (x - 1) and upper (y - 1) values <= step
+/// border_to_check - values of border as y -> x, axes_sizes - two usize, first - Y axis, second - X axis.
+/// fault_offsets - displacement of every value by faults (y -> x), limits are checked for values
+/// before displacement and step overflow is allowed between values with different displacement
+/// (along fault trace). Border and fault offsets must contain Y size * X size values
+pub fn validate_layer(
+    layer_id: usize,
+    border_to_check: &[u32],
+    axes_sizes: (usize, usize),
+    limits: [u32; 2],
+    step: Option<u16>,
+    fault_offsets: Option<&[i64]>,
+) -> Result<(), BorderValidationError> {
+    let (ax_y_size, ax_x_size) = axes_sizes;
+    let expected = ax_y_size * ax_x_size;
+    for found in std::iter::once(border_to_check.len()).chain(fault_offsets.map(|offsets| offsets.len())) {
+        if found != expected {
+            return Err(BorderValidationError::SizeMismatch { layer_id, expected, found })
+        }
     }
+    if ax_y_size < 2 || ax_x_size < 2 {
+        return Err(BorderValidationError::GridTooSmall { layer_id })
+    }
+
+    let offset = |id: usize| fault_offsets.map_or(0, |offsets| offsets[id]);
 
-    for (now_y_id, now_y) in border_to_check.iter().enumerate().skip(1) {
-        let mut pr_val = now_y[0];
-        if pr_val < limits[0] || pr_val > limits[1] {
-            panic!("Element y - {now_y_id}; x - 0: out of limits bounds");
+    let is_step_overflow = |first: usize, second: usize| {
+        let Some(step) = step else { return false };
+        if offset(first) != offset(second) {
+            return false
         }
+        border_to_check[first].abs_diff(border_to_check[second]) > step as u32
+    };
 
-        for (now_x_id, now_x) in now_y.iter().enumerate().skip(1) {
-            if *now_x < limits[0] || *now_x > limits[1] {
-                panic!("Element y - {now_y_id}; x - {now_x_id}: out of limits bounds");
-            }
+    for (now_id, now_value) in border_to_check.iter().enumerate() {
+        let (now_y_id, now_x_id) = (now_id / ax_x_size, now_id % ax_x_size);
 
-            if step.is_some() && pr_val.abs_diff(*now_x) as u16 > step.unwrap() {
-                panic!("Element y - {now_y_id}; x - {now_x_id}: step overflow PREVIOUS");
-            }
+        let original_value = *now_value as i64 - offset(now_id);
+        if original_value < limits[0] as i64 || original_value > limits[1] as i64 {
+            return Err(BorderValidationError::OutOfLimits { layer_id, y: now_y_id, x: now_x_id })
+        }
 
-            if step.is_some() && border_to_check[now_y_id-1][now_x_id].abs_diff(*now_x) as u16 > step.unwrap() {
-                panic!("Element y - {now_y_id}; x - {now_x_id}: step overflow UPPER");
-            }
+        if now_x_id != 0 && is_step_overflow(now_id - 1, now_id) {
+            return Err(BorderValidationError::StepOverflowLeft { layer_id, y: now_y_id, x: now_x_id })
+        }
 
-            pr_val = *now_x;
+        if now_y_id != 0 && is_step_overflow(now_id - ax_x_size, now_id) {
+            return Err(BorderValidationError::StepOverflowUpper { layer_id, y: now_y_id, x: now_x_id })
         }
     }
+
+    Ok(())
 }
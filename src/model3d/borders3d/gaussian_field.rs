@@ -0,0 +1,262 @@
+use rand::Rng;
+
+use crate::types::CovarianceModel;
+
+// Maximal number of cells in circulant embedding, bigger embeddings need too much memory
+const MAX_EMBEDDING_SIZE: usize = 1 << 22;
+
+// Negative eigenvalues smaller than this part of the biggest one are rounding errors of FFT
+const EIGEN_TOLERANCE: f64 = 1e-6;
+
+// Possible errors during circulant embedding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingError {
+    /// Regular grid of axes needs embedding bigger than MAX_EMBEDDING_SIZE
+    TooBig { size: usize },
+    /// Embedding has negative eigenvalues for every size up to MAX_EMBEDDING_SIZE
+    NotDefinite,
+}
+
+// Circulant embedding of covariance on torus, it depends only on axes and covariance, so it is
+// shared between borders with the same covariance
+pub struct Embedding {
+    grid_y: RegularGrid,
+    grid_x: RegularGrid,
+    torus_y_size: usize,
+    torus_x_size: usize,
+    // Square roots of eigenvalues divided by torus size
+    weights: Vec<f64>,
+}
+
+/// Function to build circulant embedding of covariance on regular grid with the smallest step of
+/// axis, so FFT is used and generation of every layer takes O(n log n).
+/// Torus is at least twice bigger than grid and is doubled while embedding has negative
+/// eigenvalues, error is returned if it becomes bigger than MAX_EMBEDDING_SIZE
+/// model - covariance model of structured part of field
+/// range - practical range of covariance in axis units
+/// sill - variance of structured part of field
+/// centers - two slices of blocks centers, first - Y axis, second - X axis.
+pub fn embedding(model: CovarianceModel, range: f32, sill: f32, centers: (&[f64], &[f64])) -> Result<Embedding, EmbeddingError> {
+    let (range, sill) = (range as f64, sill as f64);
+
+    let grid_y = RegularGrid::new(centers.0);
+    let grid_x = RegularGrid::new(centers.1);
+
+    // Sizes are checked before doubling, so they can't overflow
+    let too_big = EmbeddingError::TooBig { size: grid_y.size.saturating_mul(grid_x.size).saturating_mul(4) };
+    if grid_y.size > MAX_EMBEDDING_SIZE || grid_x.size > MAX_EMBEDDING_SIZE {
+        return Err(too_big)
+    }
+    let mut torus_y_size = (grid_y.size * 2).next_power_of_two();
+    let mut torus_x_size = (grid_x.size * 2).next_power_of_two();
+    if torus_y_size * torus_x_size > MAX_EMBEDDING_SIZE {
+        return Err(too_big)
+    }
+
+    while torus_y_size * torus_x_size <= MAX_EMBEDDING_SIZE {
+        let torus_size = torus_y_size * torus_x_size;
+
+        let mut eigens = vec![0f64; torus_size];
+        for now_y in 0..torus_y_size {
+            let dist_y = now_y.min(torus_y_size - now_y) as f64 * grid_y.step;
+            for now_x in 0..torus_x_size {
+                let dist_x = now_x.min(torus_x_size - now_x) as f64 * grid_x.step;
+                eigens[now_y * torus_x_size + now_x] = covariance(model, range, sill, dist_y.hypot(dist_x));
+            }
+        }
+        fft_2d_even(&mut eigens, torus_y_size, torus_x_size);
+
+        let max_eigen = eigens.iter().copied().fold(0.0, f64::max);
+        let min_eigen = eigens.iter().copied().fold(0.0, f64::min);
+        if min_eigen >= -EIGEN_TOLERANCE * max_eigen {
+            let weights = eigens.iter().map(|eigen| (eigen.max(0.0) / torus_size as f64).sqrt()).collect();
+            return Ok(Embedding { grid_y, grid_x, torus_y_size, torus_x_size, weights })
+        }
+
+        torus_y_size *= 2;
+        torus_x_size *= 2;
+    }
+
+    Err(EmbeddingError::NotDefinite)
+}
+
+/// Function to generate layer as gaussian random field with covariance of embedding, field is
+/// sampled at blocks centers.
+/// Mean of field is middle of limits, every value is clamped into limits and lower bound.
+/// embedding - circulant embedding of covariance built for the same centers
+/// nugget - variance of uncorrelated part of field
+/// centers - two slices of blocks centers, first - Y axis, second - X axis.
+/// limits - [min_bound, max_bound]
+/// lower_bounds - min value of every value of layer (y -> x), if exists
+/// layer - values of layer as y -> x, every value is replaced
+/// rng - random generator of this layer
+pub fn generate_layer<R: Rng>(
+    embedding: &Embedding,
+    nugget: f32,
+    centers: (&[f64], &[f64]),
+    limits: [u32; 2],
+    lower_bounds: Option<&[u32]>,
+    layer: &mut [u32],
+    rng: &mut R,
+) {
+    let torus_x_size = embedding.torus_x_size;
+
+    let mut field_re = vec![0f64; embedding.weights.len()];
+    let mut field_im = vec![0f64; embedding.weights.len()];
+    for (now_id, weight) in embedding.weights.iter().enumerate() {
+        field_re[now_id] = weight * standard_normal(rng);
+        field_im[now_id] = weight * standard_normal(rng);
+    }
+    fft_2d(&mut field_re, &mut field_im, embedding.torus_y_size, torus_x_size);
+
+    let limits_middle = (limits[0] as f64 + limits[1] as f64) / 2.0;
+    let nugget_std = (nugget as f64).sqrt();
+
+    let x_size = centers.1.len();
+    for (now_y_id, (y_center, x_axis_border)) in centers.0.iter().zip(layer.chunks_exact_mut(x_size)).enumerate() {
+        let now_y = embedding.grid_y.index(*y_center);
+        for (now_x_id, (x_center, now_border_value)) in centers.1.iter().zip(x_axis_border.iter_mut()).enumerate() {
+            let now_x = embedding.grid_x.index(*x_center);
+            let now_value = limits_middle + field_re[now_y * torus_x_size + now_x] + nugget_std * standard_normal(rng);
+            let lower_bound = lower_bounds.map_or(limits[0], |bounds| bounds[now_y_id * x_size + now_x_id].min(limits[1]));
+            *now_border_value = now_value.round().clamp(lower_bound as f64, limits[1] as f64) as u32;
+        }
+    }
+}
+
+// Regular grid which covers every center of axis
+struct RegularGrid {
+    start: f64,
+    step: f64,
+    size: usize,
+}
+
+impl RegularGrid {
+    fn new(centers: &[f64]) -> RegularGrid {
+        let start = centers[0];
+        let step = centers.windows(2).map(|pair| pair[1] - pair[0]).fold(f64::INFINITY, f64::min);
+        if !step.is_finite() {
+            return RegularGrid { start, step: 1.0, size: 1 }
+        }
+
+        let size = ((centers[centers.len() - 1] - start) / step).round() as usize + 1;
+        RegularGrid { start, step, size }
+    }
+
+    fn index(&self, center: f64) -> usize {
+        (((center - self.start) / self.step).round() as usize).min(self.size - 1)
+    }
+}
+
+// Covariance between two points at distance, range is practical range (95% of sill reached)
+fn covariance(model: CovarianceModel, range: f64, sill: f64, distance: f64) -> f64 {
+    let rel_distance = distance / range;
+    match model {
+        CovarianceModel::Spherical => {
+            if rel_distance < 1.0 {
+                sill * (1.0 - 1.5 * rel_distance + 0.5 * rel_distance.powi(3))
+            } else {
+                0.0
+            }
+        },
+        CovarianceModel::Exponential => sill * (-3.0 * rel_distance).exp(),
+        CovarianceModel::Gaussian => sill * (-3.0 * rel_distance * rel_distance).exp(),
+    }
+}
+
+// Box-Muller transform to get value from standard normal distribution
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let first: f64 = 1.0 - rng.gen::<f64>();
+    let second: f64 = rng.gen();
+    (-2.0 * first.ln()).sqrt() * (std::f64::consts::TAU * second).cos()
+}
+
+// 2D FFT for row-major data, both sizes must be powers of two
+fn fft_2d(re: &mut [f64], im: &mut [f64], y_size: usize, x_size: usize) {
+    for now_y in 0..y_size {
+        let row = now_y * x_size..(now_y + 1) * x_size;
+        fft(&mut re[row.clone()], &mut im[row]);
+    }
+
+    let mut column_re = vec![0f64; y_size];
+    let mut column_im = vec![0f64; y_size];
+    for now_x in 0..x_size {
+        for now_y in 0..y_size {
+            column_re[now_y] = re[now_y * x_size + now_x];
+            column_im[now_y] = im[now_y * x_size + now_x];
+        }
+        fft(&mut column_re, &mut column_im);
+        for now_y in 0..y_size {
+            re[now_y * x_size + now_x] = column_re[now_y];
+            im[now_y * x_size + now_x] = column_im[now_y];
+        }
+    }
+}
+
+// 2D FFT for row-major data which is real and even along both axes (covariance on torus), so
+// its transform is real too. Two rows (columns) are transformed as one complex sequence and
+// real and imaginary parts of result are their transforms, both sizes must be even powers of two
+fn fft_2d_even(values: &mut [f64], y_size: usize, x_size: usize) {
+    let mut row_re = vec![0f64; x_size];
+    let mut row_im = vec![0f64; x_size];
+    for now_y in (0..y_size).step_by(2) {
+        row_re.copy_from_slice(&values[now_y * x_size..(now_y + 1) * x_size]);
+        row_im.copy_from_slice(&values[(now_y + 1) * x_size..(now_y + 2) * x_size]);
+        fft(&mut row_re, &mut row_im);
+        values[now_y * x_size..(now_y + 1) * x_size].copy_from_slice(&row_re);
+        values[(now_y + 1) * x_size..(now_y + 2) * x_size].copy_from_slice(&row_im);
+    }
+
+    let mut column_re = vec![0f64; y_size];
+    let mut column_im = vec![0f64; y_size];
+    for now_x in (0..x_size).step_by(2) {
+        for now_y in 0..y_size {
+            column_re[now_y] = values[now_y * x_size + now_x];
+            column_im[now_y] = values[now_y * x_size + now_x + 1];
+        }
+        fft(&mut column_re, &mut column_im);
+        for now_y in 0..y_size {
+            values[now_y * x_size + now_x] = column_re[now_y];
+            values[now_y * x_size + now_x + 1] = column_im[now_y];
+        }
+    }
+}
+
+// Iterative radix-2 FFT, size must be power of two
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let size = re.len();
+
+    let mut reversed = 0;
+    for now_id in 1..size {
+        let mut bit = size >> 1;
+        while reversed & bit != 0 {
+            reversed ^= bit;
+            bit >>= 1;
+        }
+        reversed |= bit;
+        if now_id < reversed {
+            re.swap(now_id, reversed);
+            im.swap(now_id, reversed);
+        }
+    }
+
+    let mut length = 2;
+    while length <= size {
+        let angle = -std::f64::consts::TAU / length as f64;
+        for start in (0..size).step_by(length) {
+            for now_id in 0..length / 2 {
+                let (w_im, w_re) = (angle * now_id as f64).sin_cos();
+                let (first, second) = (start + now_id, start + now_id + length / 2);
+
+                let t_re = re[second] * w_re - im[second] * w_im;
+                let t_im = re[second] * w_im + im[second] * w_re;
+
+                re[second] = re[first] - t_re;
+                im[second] = im[first] - t_im;
+                re[first] += t_re;
+                im[first] += t_im;
+            }
+        }
+        length <<= 1;
+    }
+}
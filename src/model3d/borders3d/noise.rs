@@ -0,0 +1,107 @@
+use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+// Gradients of noise lattice, every lattice node takes one of them based on hash
+const GRADIENTS: [(f64, f64); 8] = [
+    (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2), (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2), (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+/// Function to generate layer based on fractal gradient (Perlin) noise.
+/// Noise is calculated at physical coordinates of blocks centers, so surface doesn't depend on
+/// axis resolution.
+/// octaves - number of summed noises, every next noise has 2 times smaller wavelength
+/// persistence - amplitude multiplier for every next octave
+/// wavelength - wavelength of first octave in axis units
+/// centers - two slices of blocks centers, first - Y axis, second - X axis.
+/// limits - [min_bound, max_bound]
+/// lower_bounds - min value of every value of layer (y -> x), if exists noise is scaled between
+/// lower bound and max bound
+/// layer - values of layer as y -> x, every value is replaced
+/// rng - random generator of this layer
+#[allow(clippy::too_many_arguments)]
+pub fn generate_layer<R: Rng>(
+    octaves: u8,
+    persistence: f32,
+    wavelength: f32,
+    centers: (&[f64], &[f64]),
+    limits: [u32; 2],
+    lower_bounds: Option<&[u32]>,
+    layer: &mut [u32],
+    rng: &mut R,
+) {
+    let noise = PerlinNoise::new(rng);
+
+    // Every octave is shifted to avoid zero values at same lattice nodes
+    let octaves_params: Vec<(f64, f64, (f64, f64))> = (0..octaves as i32).map(|now_octave| {
+        let frequency = 2f64.powi(now_octave) / wavelength as f64;
+        let amplitude = (persistence as f64).powi(now_octave);
+        (frequency, amplitude, (rng.gen_range(0.0..256.0), rng.gen_range(0.0..256.0)))
+    }).collect();
+    let amplitudes_sum: f64 = octaves_params.iter().map(|(_, amplitude, _)| amplitude).sum();
+
+    let x_size = centers.1.len();
+    for (now_y, (y_center, x_axis_border)) in centers.0.iter().zip(layer.chunks_exact_mut(x_size)).enumerate() {
+        for (now_x, (x_center, now_border_value)) in centers.1.iter().zip(x_axis_border.iter_mut()).enumerate() {
+            let mut now_value = 0.0;
+            for (frequency, amplitude, offset) in &octaves_params {
+                now_value += amplitude * noise.value(x_center * frequency + offset.0, y_center * frequency + offset.1);
+            }
+
+            // Noise is between -1 and 1, it is scaled into limits
+            let now_value = ((now_value / amplitudes_sum).clamp(-1.0, 1.0) + 1.0) / 2.0;
+            let lower_bound = lower_bounds.map_or(limits[0], |bounds| bounds[now_y * x_size + now_x].min(limits[1]));
+            *now_border_value = lower_bound + (now_value * (limits[1] - lower_bound) as f64).round() as u32;
+        }
+    }
+}
+
+// Classic 2D gradient noise with random permutation table
+struct PerlinNoise {
+    permutation: [u8; 512],
+}
+
+impl PerlinNoise {
+    fn new<R: Rng>(rng: &mut R) -> PerlinNoise {
+        let mut base: Vec<u8> = (0..=255).collect();
+        base.shuffle(rng);
+
+        let mut permutation = [0u8; 512];
+        for (now_id, now_value) in permutation.iter_mut().enumerate() {
+            *now_value = base[now_id % 256];
+        }
+
+        PerlinNoise { permutation }
+    }
+
+    // Returns noise value between -1 and 1
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let (x_floor, y_floor) = (x.floor(), y.floor());
+        let (x_cell, y_cell) = ((x_floor as i64 & 255) as usize, (y_floor as i64 & 255) as usize);
+        let (x_rel, y_rel) = (x - x_floor, y - y_floor);
+
+        let gradient_dot = |x_shift: usize, y_shift: usize| {
+            let hash = self.permutation[self.permutation[x_cell + x_shift] as usize + y_cell + y_shift];
+            let gradient = GRADIENTS[(hash & 7) as usize];
+            gradient.0 * (x_rel - x_shift as f64) + gradient.1 * (y_rel - y_shift as f64)
+        };
+
+        let (x_fade, y_fade) = (fade(x_rel), fade(y_rel));
+        let lower = lerp(gradient_dot(0, 0), gradient_dot(1, 0), x_fade);
+        let upper = lerp(gradient_dot(0, 1), gradient_dot(1, 1), x_fade);
+
+        // Max value of 2D gradient noise with unit gradients is sqrt(0.5)
+        lerp(lower, upper, y_fade) * SQRT_2
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(first: f64, second: f64, t: f64) -> f64 {
+    first + t * (second - first)
+}
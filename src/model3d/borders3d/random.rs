@@ -1,26 +1,22 @@
+use rand::Rng;
 use rand::distributions::{Distribution, Uniform};
 
 /// Funtion to generate layer based on limits and nothing more.
-/// axes_sizes - two usize, first - Y axis, second - X axis.
 /// limits - [min_bound, max_bound]
-pub fn generate_layer(axes_sizes: (usize, usize), limits: [u32; 2]) -> Vec<Vec<u32>> {
-    let mut now_layer_borders: Vec<Vec<u32>> = Vec::with_capacity(axes_sizes.0);
+/// lower_bounds - min value of every value of layer (y -> x), if exists
+/// layer - values of layer as y -> x, every value is replaced
+/// rng - random generator of this layer
+pub fn generate_layer<R: Rng>(limits: [u32; 2], lower_bounds: Option<&[u32]>, layer: &mut [u32], rng: &mut R) {
+    if let Some(lower_bounds) = lower_bounds {
+        for (now_value, lower_bound) in layer.iter_mut().zip(lower_bounds) {
+            *now_value = rng.gen_range((*lower_bound).min(limits[1])..=limits[1]);
+        }
+        return
+    }
 
-    let mut rng = rand::thread_rng();
     let gen_range = Uniform::from(limits[0]..=limits[1]);
 
-    for _ in 0..axes_sizes.0 {
-        //TODO: Check perfomance
-        //
-        // let mut x_axis_border: Vec<u32> = Vec::with_capacity(axes_sizes.1);
-        //
-        // for _ in 0..axes_sizes.1 {
-        //     x_axis_border.push(gen_range.sample(&mut rng));
-        // }
-        // now_layer_borders.push(x_axis_border);
-
-        now_layer_borders.push(gen_range.sample_iter(&mut rng).take(axes_sizes.1).collect())
+    for (now_value, new_value) in layer.iter_mut().zip(gen_range.sample_iter(&mut *rng)) {
+        *now_value = new_value;
     }
-
-    now_layer_borders
 }
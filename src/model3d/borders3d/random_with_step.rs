@@ -1,115 +1,109 @@
 use rand::Rng;
 use rand::distributions::{Uniform, Distribution};
 
-pub fn generate_layer(step: u16, _prob: f32, axes_sizes: (usize, usize), limits: [u32; 2]) -> Vec<Vec<u32>> {
+/// Function to generate layer where difference between neighbour values <= step.
+/// prob - probability of step for every value, otherwise surface stays flat (value is taken from
+/// previous (x - 1) or upper (y - 1) value as close as step allows).
+/// axes_sizes - two usize, first - Y axis, second - X axis.
+/// limits - [min_bound, max_bound]
+/// lower_bounds - min value of every value of layer (y -> x), if exists. Layer is generated not
+/// lower than the lowest surface with max step above them, so both bounds and step are kept if
+/// this surface is inside limits
+/// layer - values of layer as y -> x, every value is replaced
+/// rng - random generator of this layer
+pub fn generate_layer<R: Rng>(
+    step: u16,
+    prob: f32,
+    axes_sizes: (usize, usize),
+    limits: [u32; 2],
+    lower_bounds: Option<&[u32]>,
+    layer: &mut [u32],
+    rng: &mut R,
+) {
     let step = step as u32;
+    let prob = prob as f64;
 
-    // Bounds when it is reasonable to check possible limites overflow
-    let lower_check = limits[0].saturating_add(step);
-    let upper_check = limits[1].saturating_sub(step);
+    let floor = lower_bounds.map(|lower_bounds| step_envelope(lower_bounds, axes_sizes.1, step));
+    let floor = |id: usize| floor.as_ref().map_or(limits[0], |floor| floor[id]);
 
-    let mut rng = rand::thread_rng();
-    let limits_rng = Uniform::from(limits[0]..=limits[1]);
-    let step_rng = Uniform::from(-(step as i32)..=step as i32);
+    let (first_row, other_rows) = layer.split_at_mut(axes_sizes.1);
 
-    let mut now_layer_borders: Vec<Vec<u32>> = Vec::with_capacity(axes_sizes.0);
-    let mut x_axis_border: Vec<u32> = Vec::with_capacity(axes_sizes.1);
-
-    let mut pr_val = limits_rng.sample(&mut rng);
-    x_axis_border.push(pr_val);
+    let mut pr_val = match lower_bounds {
+        Some(_) => rng.gen_range(floor(0).min(limits[1])..=limits[1]),
+        None => Uniform::from(limits[0]..=limits[1]).sample(rng),
+    };
+    first_row[0] = pr_val;
 
     // Loop to fill only first row (y=0)
-    for _ in 1..axes_sizes.1 {
-        if pr_val < lower_check {
-            pr_val = if pr_val > upper_check {
-                limits_rng.sample(&mut rng)
-            } else {
-                rng.gen_range(limits[0]..=pr_val+step)
-            }
-        } else if pr_val > upper_check {
-            pr_val = if pr_val < lower_check {
-                limits_rng.sample(&mut rng)
-            } else {
-                rng.gen_range(pr_val-step..=limits[1])
-            }
-        } else {
-            let now_step = step_rng.sample(&mut rng);
-            if now_step > 0 {
-                pr_val += now_step as u32;
-            } else {
-                pr_val -= now_step.unsigned_abs();
-            }
-        };
-
-        x_axis_border.push(pr_val);
+    for (now_x, now_value) in first_row.iter_mut().enumerate().skip(1) {
+        let now_bounds = step_bounds(pr_val, pr_val, step, [floor(now_x), limits[1]]);
+        pr_val = next_value(now_bounds, pr_val, prob, rng);
+        *now_value = pr_val;
     }
-    now_layer_borders.push(x_axis_border);
 
     // Loop to fill every y from 1 to y size
-    for now_y in 1..axes_sizes.0 {
-        // First value gen (x = 0)
-        let mut x_axis_border: Vec<u32> = Vec::with_capacity(axes_sizes.1);
-        let pr_x_ax = &now_layer_borders[now_y - 1];
-        let mut pr_val = pr_x_ax[0];
-
-        if pr_val < lower_check {
-            pr_val = if pr_val > upper_check {
-                limits_rng.sample(&mut rng)
-            } else {
-                rng.gen_range(limits[0]..=pr_val+step)
-            }
-        } else if pr_val > upper_check {
-            pr_val = if pr_val < lower_check {
-                limits_rng.sample(&mut rng)
-            } else {
-                rng.gen_range(pr_val-step..=limits[1])
-            }
-        } else {
-            let now_step = step_rng.sample(&mut rng);
-            if now_step > 0 {
-                pr_val += now_step as u32;
-            } else {
-                pr_val -= now_step.unsigned_abs();
-            }
-        };
-        x_axis_border.push(pr_val);
-
-        // Generating every x expect 0
-        for upper_value in pr_x_ax.iter().skip(1) {
-            if *upper_value >= pr_val {
-                let now_up_limit = if pr_val > upper_check {
-                    limits[1]
-                } else {
-                    pr_val + step
-                };
-
-                let now_down_limit = if *upper_value < lower_check {
-                    limits[0]
-                } else {
-                    upper_value - step
-                };
-
-                pr_val = rng.gen_range(now_down_limit..=now_up_limit);
-            } else {
-                let now_up_limit = if *upper_value > upper_check {
-                    limits[1]
-                } else {
-                    upper_value + step
-                };
-
-                let now_down_limit = if pr_val < lower_check {
-                    limits[0]
-                } else {
-                    pr_val - step
-                };
-
-                pr_val = rng.gen_range(now_down_limit..=now_up_limit);
-            }
-            x_axis_border.push(pr_val);
+    let mut pr_x_ax: &[u32] = first_row;
+    for (now_y, x_axis_border) in other_rows.chunks_exact_mut(axes_sizes.1).enumerate() {
+        let row_start = (now_y + 1) * axes_sizes.1;
+
+        // First value gen (x = 0), depends only on upper value
+        let now_bounds = step_bounds(pr_x_ax[0], pr_x_ax[0], step, [floor(row_start), limits[1]]);
+        let mut pr_val = next_value(now_bounds, pr_x_ax[0], prob, rng);
+        x_axis_border[0] = pr_val;
+
+        // Generating every x expect 0, depends on previous and upper values
+        for (now_x, (now_value, upper_value)) in x_axis_border.iter_mut().zip(pr_x_ax.iter()).enumerate().skip(1) {
+            let now_bounds = step_bounds(pr_val, *upper_value, step, [floor(row_start + now_x), limits[1]]);
+            pr_val = next_value(now_bounds, pr_val, prob, rng);
+            *now_value = pr_val;
         }
 
-        now_layer_borders.push(x_axis_border);
+        pr_x_ax = x_axis_border;
     }
+}
+
+// Function to find the lowest surface not lower than bounds where difference between neighbour
+// values <= step, two passes are enough because every value depends on its 4 neighbours
+fn step_envelope(lower_bounds: &[u32], x_size: usize, step: u32) -> Vec<u32> {
+    let mut envelope = lower_bounds.to_vec();
+    let size = envelope.len();
 
-    now_layer_borders
+    for now_id in 0..size {
+        if now_id % x_size != 0 {
+            envelope[now_id] = envelope[now_id].max(envelope[now_id - 1].saturating_sub(step));
+        }
+        if now_id >= x_size {
+            envelope[now_id] = envelope[now_id].max(envelope[now_id - x_size].saturating_sub(step));
+        }
+    }
+
+    for now_id in (0..size).rev() {
+        if now_id % x_size != x_size - 1 {
+            envelope[now_id] = envelope[now_id].max(envelope[now_id + 1].saturating_sub(step));
+        }
+        if now_id + x_size < size {
+            envelope[now_id] = envelope[now_id].max(envelope[now_id + x_size].saturating_sub(step));
+        }
+    }
+
+    envelope
+}
+
+// Function to find bounds for value, which differs from both neighbours not more than step and
+// stays inside limits. If it's impossible value stays as close to limits as step allows
+fn step_bounds(pr_value: u32, upper_value: u32, step: u32, limits: [u32; 2]) -> [u32; 2] {
+    let now_up_limit = pr_value.min(upper_value).saturating_add(step).min(limits[1]);
+    let now_down_limit = pr_value.max(upper_value).saturating_sub(step).max(limits[0]).min(now_up_limit);
+
+    [now_down_limit, now_up_limit]
+}
+
+// Function to pick next value: with probability prob it is random value inside bounds, otherwise
+// it is flat value moved inside bounds
+fn next_value<R: Rng>(bounds: [u32; 2], flat_value: u32, prob: f64, rng: &mut R) -> u32 {
+    if rng.gen_bool(prob) {
+        rng.gen_range(bounds[0]..=bounds[1])
+    } else {
+        flat_value.clamp(bounds[0], bounds[1])
+    }
 }
@@ -0,0 +1,97 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rand::RngCore;
+
+use crate::model3d::{available_threads, generate_model3d_threads, part_rng, BorderValidationError, Model3D, DATASET_STREAM};
+use crate::types::Params3D;
+
+// Possible errors during dataset generation
+#[derive(Debug)]
+pub enum DatasetError {
+    Generation { model_num: usize, error: BorderValidationError },
+    Export { model_num: usize, error: io::Error },
+}
+
+impl std::fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DatasetError::Generation { model_num, error } => write!(f, "Model {model_num}: {error}"),
+            DatasetError::Export { model_num, error } => write!(f, "Model {model_num}: export failed, {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {}
+
+/// Returns seed of model model_num in dataset with master seed, seeds of different models are
+/// independent, so models don't repeat each other
+pub fn dataset_seed(seed: u64, model_num: usize) -> u64 {
+    let mut rng = part_rng(seed, DATASET_STREAM);
+    rng.set_word_pos(model_num as u128 * 2);
+    rng.next_u64()
+}
+
+/// Generates count models in parallel and returns them ordered by number, model n is generated
+/// with dataset_seed(seed, n), so dataset doesn't depend on number of threads
+pub fn generate_dataset(params: Arc<Params3D>, count: usize, seed: u64) -> Result<Vec<Model3D>, DatasetError> {
+    let models: Mutex<Vec<Option<Model3D>>> = Mutex::new(vec![None; count]);
+
+    generate_dataset_with(params, count, seed, |model_num, model, _done| {
+        models.lock().unwrap()[model_num] = Some(model);
+        Ok(())
+    })?;
+
+    Ok(models.into_inner().unwrap().into_iter().flatten().collect())
+}
+
+/// Generates count models in scoped threads (one per available core) and passes every model to
+/// on_model(model_num, model, done) as soon as it is generated, done is number of generated models.
+/// Models are dropped after on_model, so it can export them without keeping whole dataset in
+/// memory. Every thread shares the same params. Cores are shared between models, so borders of
+/// one model are generated in parallel only if there are less models than cores
+/// First error stops generation of new models
+pub fn generate_dataset_with<F>(params: Arc<Params3D>, count: usize, seed: u64, on_model: F) -> Result<(), DatasetError>
+where
+    F: Fn(usize, Model3D, usize) -> Result<(), io::Error> + Sync,
+{
+    let available_threads = available_threads();
+    let threads_count = available_threads.min(count);
+    let borders_threads_count = (available_threads / threads_count.max(1)).max(1);
+
+    let next_model = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let first_error: Mutex<Option<DatasetError>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads_count {
+            scope.spawn(|| {
+                while !failed.load(Ordering::Relaxed) {
+                    let model_num = next_model.fetch_add(1, Ordering::Relaxed);
+                    if model_num >= count {
+                        break
+                    }
+
+                    let result = generate_model3d_threads(params.clone(), dataset_seed(seed, model_num), borders_threads_count)
+                        .map_err(|error| DatasetError::Generation { model_num, error })
+                        .and_then(|model| {
+                            let now_done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                            on_model(model_num, model, now_done).map_err(|error| DatasetError::Export { model_num, error })
+                        });
+
+                    if let Err(error) = result {
+                        failed.store(true, Ordering::Relaxed);
+                        first_error.lock().unwrap().get_or_insert(error);
+                    }
+                }
+            });
+        }
+    });
+
+    match first_error.into_inner().unwrap() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
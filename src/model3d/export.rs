@@ -1,5 +1,6 @@
 use std::fs::File;
-use std::io::Write;
+use std::fmt::Write as _;
+use std::io::{BufWriter, Write};
 
 use numtoa::NumToA;
 use nanoserde::SerJson;
@@ -7,59 +8,299 @@ use nanoserde::SerJson;
 use crate::model3d::Model3D;
 use crate::types::Params3D;
 
+/// Sections and formatting of exported model, nothing is selected by default
+/// # Examples
+/// ```
+/// use grunt::ExportOptions;
+/// let options = ExportOptions::new().with_params().with_borders().with_float_precision(3);
+/// assert!(options.params() && options.borders() && !options.voxels());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    params: bool,
+    borders: bool,
+    fill_values: bool,
+    voxels: bool,
+    statistics: bool,
+    metadata: bool,
+    float_precision: Option<usize>,
+    index_base: usize,
+}
+
+impl ExportOptions {
+    pub fn new() -> ExportOptions {
+        ExportOptions::default()
+    }
+
+    /// Options with every section selected
+    pub fn all() -> ExportOptions {
+        ExportOptions::new()
+            .with_params()
+            .with_borders()
+            .with_fill_values()
+            .with_voxels()
+            .with_statistics()
+            .with_metadata()
+    }
+
+    pub fn with_params(mut self) -> ExportOptions {
+        self.params = true;
+        self
+    }
+
+    pub fn with_borders(mut self) -> ExportOptions {
+        self.borders = true;
+        self
+    }
+
+    pub fn with_fill_values(mut self) -> ExportOptions {
+        self.fill_values = true;
+        self
+    }
+
+    /// Voxels are exported as layer ids, they are null if model has no Z axis
+    pub fn with_voxels(mut self) -> ExportOptions {
+        self.voxels = true;
+        self
+    }
+
+    /// Statistics are min, max and mean of every border and fill values layer
+    pub fn with_statistics(mut self) -> ExportOptions {
+        self.statistics = true;
+        self
+    }
+
+    /// Metadata are crate version, seed, index base and float precision
+    pub fn with_metadata(mut self) -> ExportOptions {
+        self.metadata = true;
+        self
+    }
+
+    /// Sets number of decimal places for floats (fill values and statistics), by default floats
+    /// are written with the shortest representation
+    pub fn with_float_precision(mut self, float_precision: usize) -> ExportOptions {
+        self.float_precision = Some(float_precision);
+        self
+    }
+
+    /// Sets first index of borders, layers and rows in keys ("bo0", "y0" for 0), default is 0
+    pub fn with_index_base(mut self, index_base: usize) -> ExportOptions {
+        self.index_base = index_base;
+        self
+    }
+}
+
+impl ExportOptions {
+    pub fn params(&self) -> bool {
+        self.params
+    }
+
+    pub fn borders(&self) -> bool {
+        self.borders
+    }
+
+    pub fn fill_values(&self) -> bool {
+        self.fill_values
+    }
+
+    pub fn voxels(&self) -> bool {
+        self.voxels
+    }
+
+    pub fn statistics(&self) -> bool {
+        self.statistics
+    }
+
+    pub fn metadata(&self) -> bool {
+        self.metadata
+    }
+
+    pub fn float_precision(&self) -> Option<usize> {
+        self.float_precision
+    }
+
+    pub fn index_base(&self) -> usize {
+        self.index_base
+    }
+}
+
 impl Model3D {
-    pub fn export_model(&self, name: &str, save: &[&str]) -> Result<(), std::io::Error> {
-        let mut result = String::from("");
-        result += "{\"params3D\":";
+    /// Exports model to file {name}.json, see export_to for format
+    pub fn export_model(&self, name: &str, options: &ExportOptions) -> Result<(), std::io::Error> {
+        let file = BufWriter::new(File::create(format!("{name}.json"))?);
+        self.export_to(file, options)
+    }
+
+    /// Writes model as {"params3D":...,"borders":[{"bo0":[{"y0":[...]}]}]} to any writer,
+    /// borders are written row by row, so whole document is never kept in memory.
+    /// params3D and borders are always written, null if they are not selected.
+    /// Other sections are written only if selected:
+    /// "fill_values":[{"la0":[{"y0":[...]}]}], "voxels":[{"z0":[{"y0":[...]}]}],
+    /// "statistics":{"borders":[{"min":..,"max":..,"mean":..}],"fill_values":[...]} and
+    /// "metadata":{"version":..,"seed":..,"index_base":..,"float_precision":..}
+    pub fn export_to<W: Write>(&self, mut writer: W, options: &ExportOptions) -> Result<(), std::io::Error> {
+        writer.write_all(b"{\"params3D\":")?;
+        if options.params {
+            export_params(&mut writer, &self.params)?;
+        } else { writer.write_all(b"null")? }
+
+        writer.write_all(b",\"borders\":")?;
+        if options.borders {
+            let borders_sizes = (self.borders.borders_count(), self.borders.y_size());
+            export_grid(&mut writer, borders_sizes, |border, y| self.borders.row(border, y), "bo", options,
+                |row, value, buf| row.push_str(value.numtoa_str(10, buf)))?;
+        } else { writer.write_all(b"null")? }
+
+        if options.fill_values {
+            writer.write_all(b",\"fill_values\":")?;
+            export_grid(&mut writer, nested_sizes(&self.fill_values), |layer, y| &self.fill_values[layer][y], "la",
+                options, |row, value, _buf| push_float(row, value, options.float_precision))?;
+        }
 
-        if save.contains(&"params") {
-            export_params(&mut result, &self.params);
-        } else { result += "null" }
+        if options.voxels {
+            writer.write_all(b",\"voxels\":")?;
+            match self.to_voxels() {
+                Some(voxels) => export_grid(&mut writer, nested_sizes(&voxels), |z, y| &voxels[z][y], "z", options,
+                    |row, value, buf| row.push_str(value.numtoa_str(10, buf)))?,
+                None => writer.write_all(b"null")?,
+            }
+        }
 
-        result += ",\"borders\":";
-        if save.contains(&"borders") {
-            export_border_num(&mut result, &self.borders)
-        } else { result += "null" }
+        if options.statistics {
+            writer.write_all(b",\"statistics\":")?;
+            self.export_statistics(&mut writer, options)?;
+        }
 
-        if name == "TestModelBench.test.bench" { return Ok(()) }
+        if options.metadata {
+            writer.write_all(b",\"metadata\":")?;
+            self.export_metadata(&mut writer, options)?;
+        }
+        writer.write_all(b"}")?;
 
-        let mut file = File::create(format!("{name}.json"))?;
-        file.write_all(result.as_bytes())?;
-        Ok(())
+        writer.flush()
+    }
+
+    fn export_statistics<W: Write>(&self, writer: &mut W, options: &ExportOptions) -> Result<(), std::io::Error> {
+        let borders_stats: Vec<[f64; 3]> = self.borders.borders().map(|border| {
+            values_stats(border.iter().map(|value| *value as f64))
+        }).collect();
+        let fill_values_stats: Vec<[f64; 3]> = self.fill_values.iter().map(|layer| {
+            values_stats(layer.iter().flatten().map(|value| *value as f64))
+        }).collect();
+
+        let mut result = String::from("{");
+        for (section_num, (section, stats)) in [("borders", borders_stats), ("fill_values", fill_values_stats)]
+            .iter().enumerate()
+        {
+            if section_num != 0 {
+                result.push(',');
+            }
+            result += format!("\"{section}\":[").as_str();
+            for (stats_num, [min, max, mean]) in stats.iter().enumerate() {
+                if stats_num != 0 {
+                    result.push(',');
+                }
+                result += "{\"min\":";
+                push_float(&mut result, *min, options.float_precision);
+                result += ",\"max\":";
+                push_float(&mut result, *max, options.float_precision);
+                result += ",\"mean\":";
+                push_float(&mut result, *mean, options.float_precision);
+                result.push('}');
+            }
+            result.push(']');
+        }
+        result.push('}');
+
+        writer.write_all(result.as_bytes())
+    }
+
+    fn export_metadata<W: Write>(&self, writer: &mut W, options: &ExportOptions) -> Result<(), std::io::Error> {
+        let seed = self.seed.map_or(String::from("null"), |seed| seed.to_string());
+        let float_precision = options.float_precision.map_or(String::from("null"), |precision| precision.to_string());
+
+        write!(writer, "{{\"version\":\"{}\",\"seed\":{seed},\"index_base\":{},\"float_precision\":{float_precision}}}",
+            env!("CARGO_PKG_VERSION"), options.index_base)
     }
 }
 
-fn export_border_num(result: &mut String, borders: &[Vec<Vec<u32>>]){
-    let mut buf = [0u8; 12]; *result += "[";
-    for (depth_num, depth) in borders.iter().enumerate() {
-        *result += "{\"bo";
-        *result += format!("{depth_num}\":[").as_str();
-        for (y_num, y_axis) in depth.iter().enumerate() {
-            *result += "{\"y";
-            *result += format!("{y_num}\":[").as_str();
+// Function to write 3D grid as [{"<prefix>0":[{"y0":[...]}, ...]}, ...]
+// grid_sizes - number of planes and rows in every plane, grid_row returns row by plane and y,
+// values are added to row by push_value
+fn export_grid<'a, W: Write, T: 'a>(
+    writer: &mut W,
+    grid_sizes: (usize, usize),
+    grid_row: impl Fn(usize, usize) -> &'a [T],
+    prefix: &str,
+    options: &ExportOptions,
+    push_value: impl Fn(&mut String, &T, &mut [u8; 20]),
+) -> Result<(), std::io::Error> {
+    let mut buf = [0u8; 20];
+    let mut row = String::new();
+    let (depth_count, y_count) = grid_sizes;
+
+    writer.write_all(b"[")?;
+    for depth_num in 0..depth_count {
+        write!(writer, "{{\"{prefix}{}\":[", depth_num + options.index_base)?;
+        for y_num in 0..y_count {
+            let y_axis = grid_row(depth_num, y_num);
+            row.clear();
+            row += "{\"y";
+            row.push_str((y_num + options.index_base).numtoa_str(10, &mut buf));
+            row += "\":[";
 
-            result.push_str(y_axis[0].numtoa_str(10, &mut buf));
+            push_value(&mut row, &y_axis[0], &mut buf);
 
             for x in y_axis[1..].iter() {
-                result.push(',');
-                result.push_str(x.numtoa_str(10, &mut buf));
+                row.push(',');
+                push_value(&mut row, x, &mut buf);
             }
 
-            if y_num != depth.len() - 1 {
-                *result += "]},"
+            if y_num != y_count - 1 {
+                row += "]},"
             } else {
-                *result += "]}"
+                row += "]}"
             }
+            writer.write_all(row.as_bytes())?;
         }
-        if depth_num != borders.len() - 1 {
-            *result += "]},";
+        if depth_num != depth_count - 1 {
+            writer.write_all(b"]},")?;
         } else {
-            *result += "]}";
+            writer.write_all(b"]}")?;
         }
     }
-    *result += "]";
+    writer.write_all(b"]")
 }
 
-fn export_params(result: &mut String, params: &Params3D) {
-    result.push_str(SerJson::serialize_json(params).as_str());
+// Function to get number of planes and rows of nested grid
+fn nested_sizes<T>(grid: &[Vec<Vec<T>>]) -> (usize, usize) {
+    (grid.len(), grid.first().map_or(0, |plane| plane.len()))
+}
+
+fn export_params<W: Write>(writer: &mut W, params: &Params3D) -> Result<(), std::io::Error> {
+    writer.write_all(SerJson::serialize_json(params).as_bytes())
+}
+
+// Function to add float with given number of decimal places or with the shortest representation
+fn push_float<T: std::fmt::Display>(result: &mut String, value: T, float_precision: Option<usize>) {
+    let _ = match float_precision {
+        Some(precision) => write!(result, "{value:.precision$}"),
+        None => write!(result, "{value}"),
+    };
+}
+
+// Function to get min, max and mean of values, all of them are 0 for empty values
+fn values_stats(values: impl Iterator<Item = f64>) -> [f64; 3] {
+    let (mut min, mut max, mut sum, mut count) = (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0usize);
+    for value in values {
+        min = min.min(value);
+        max = max.max(value);
+        sum += value;
+        count += 1;
+    }
+
+    match count {
+        0 => [0.0; 3],
+        _ => [min, max, sum / count as f64],
+    }
 }
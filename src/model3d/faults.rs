@@ -0,0 +1,47 @@
+use crate::types::Params3D;
+use crate::model3d::{border_z, BorderGrid};
+use crate::model3d::borders3d::{border_rules, validate_layer, BorderValidationError};
+
+/// Function to displace borders on hanging wall of every fault from params one by one.
+/// Depth of border is taken from axis_z edges if it exists, otherwise border value is depth.
+/// Displacement is vertical, so borders from different sides of fault can cross near fault plane.
+/// After displacement every border is validated again, step overflow is allowed only along traces
+/// of faults
+pub fn apply_faults(params: &Params3D, borders: &mut BorderGrid) -> Result<(), BorderValidationError> {
+    let faults = params.faults();
+    if faults.is_empty() {
+        return Ok(())
+    }
+
+    let axis_x = params.axis_x();
+    let axis_y = params.axis_y();
+    let axis_z = params.axis_z();
+    // Depth of border is counted from top of model
+    let top_z = axis_z.as_ref().map_or(0.0, |axis_z| axis_z.start());
+    let axes_sizes = (axis_y.blocks_count(), axis_x.blocks_count());
+    let borders_params = params.borders();
+
+    for (now_border_id, now_border) in borders.borders_mut().enumerate() {
+        let mut fault_offsets = vec![0i64; now_border.len()];
+
+        for fault in faults.iter() {
+            for (now_id, (now_value, now_offset)) in now_border.iter_mut().zip(fault_offsets.iter_mut()).enumerate() {
+                let y_center = axis_y.center(now_id / axes_sizes.1);
+                let x_center = axis_x.center(now_id % axes_sizes.1);
+                let now_depth = border_z(*now_value, axis_z.as_deref()) - top_z;
+                if !fault.is_hanging_wall(x_center, y_center, now_depth) {
+                    continue
+                }
+
+                let displaced_value = (*now_value as i64 + fault.throw() as i64).clamp(0, u32::MAX as i64);
+                *now_offset += displaced_value - *now_value as i64;
+                *now_value = displaced_value as u32;
+            }
+        }
+
+        let (now_limits, now_step) = border_rules(&borders_params, now_border_id);
+        validate_layer(now_border_id, now_border, axes_sizes, now_limits, now_step, Some(&fault_offsets))?;
+    }
+
+    Ok(())
+}
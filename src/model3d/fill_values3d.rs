@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::types::{FillType, FillValues, Params3D};
+use crate::model3d::{part_rng, FILL_VALUES_STREAM};
+
+/// Function to generate fill values for every layer of model
+/// Layers are placed between borders: layer 0 is above border 0, layer n is below border n - 1,
+/// so model with n borders always has n + 1 layers.
+/// Fill values are represented as layer_num -> y -> x
+/// Every layer is generated with its own random stream based on seed
+pub fn generate_fill_values(params: Arc<Params3D>, seed: u64) -> Vec<Vec<Vec<f32>>> {
+    let ax_y_size = params.axis_y().blocks_count();
+    let ax_x_size = params.axis_x().blocks_count();
+
+    let fill_presets = params.fill_values();
+    let number_of_layers = params.borders().number_of_borders() as usize + 1;
+
+    if fill_presets.is_empty() {
+        return Vec::new()
+    }
+
+    // How many layers already used every preset, required for ordered presets
+    let mut presets_usage = vec![0usize; fill_presets.len()];
+    let mut fill_values: Vec<Vec<Vec<f32>>> = Vec::with_capacity(number_of_layers);
+
+    for now_layer_id in 0..number_of_layers {
+        let now_preset_id = now_layer_id % fill_presets.len();
+        let now_preset = &fill_presets[now_preset_id];
+        let mut rng = part_rng(seed, FILL_VALUES_STREAM + now_layer_id as u64);
+
+        let now_fill_type = pick_fill_type(now_preset, presets_usage[now_preset_id], &mut rng);
+        presets_usage[now_preset_id] += 1;
+
+        let mut now_layer = generate_layer(now_fill_type, (ax_y_size, ax_x_size), &mut rng);
+        if now_preset.values_smooth() > 0 {
+            now_layer = smooth_layer(&now_layer, now_preset.values_smooth() as usize);
+        }
+
+        fill_values.push(now_layer);
+    }
+
+    fill_values
+}
+
+// Function to pick fill type from preset, ordered presets are used one by one, others - randomly
+fn pick_fill_type<'a, R: Rng>(preset: &'a FillValues, usage: usize, rng: &mut R) -> &'a FillType {
+    let fill_types = preset.fill_values();
+
+    if preset.is_preset_ordered() {
+        &fill_types[usage % fill_types.len()]
+    } else {
+        &fill_types[rng.gen_range(0..fill_types.len())]
+    }
+}
+
+// Function to generate values for every block of layer based on fill type
+fn generate_layer<R: Rng>(fill_type: &FillType, axes_sizes: (usize, usize), rng: &mut R) -> Vec<Vec<f32>> {
+    let mut now_layer: Vec<Vec<f32>> = Vec::with_capacity(axes_sizes.0);
+
+    for _ in 0..axes_sizes.0 {
+        let x_axis_values: Vec<f32> = match fill_type {
+            FillType::RandomBetween(lower_bound, upper_bound) => {
+                (0..axes_sizes.1).map(|_| rng.gen_range(*lower_bound..=*upper_bound) as f32).collect()
+            },
+            FillType::ValueFrom(values) => {
+                (0..axes_sizes.1).map(|_| values[rng.gen_range(0..values.len())]).collect()
+            },
+        };
+        now_layer.push(x_axis_values);
+    }
+
+    now_layer
+}
+
+// Function to smooth layer using mean value inside square window with radius values_smooth
+// Summed-area table is used, so complexity doesn't depend on radius
+fn smooth_layer(layer: &[Vec<f32>], radius: usize) -> Vec<Vec<f32>> {
+    let y_size = layer.len();
+    let x_size = layer[0].len();
+
+    let mut sums = vec![vec![0f64; x_size + 1]; y_size + 1];
+    for (now_y, now_row) in layer.iter().enumerate() {
+        for (now_x, now_value) in now_row.iter().enumerate() {
+            sums[now_y + 1][now_x + 1] = *now_value as f64 + sums[now_y][now_x + 1] + sums[now_y + 1][now_x]
+                - sums[now_y][now_x];
+        }
+    }
+
+    let mut smoothed_layer: Vec<Vec<f32>> = Vec::with_capacity(y_size);
+    for now_y in 0..y_size {
+        let y_min = now_y.saturating_sub(radius);
+        let y_max = (now_y + radius + 1).min(y_size);
+
+        let mut x_axis_values: Vec<f32> = Vec::with_capacity(x_size);
+        for now_x in 0..x_size {
+            let x_min = now_x.saturating_sub(radius);
+            let x_max = (now_x + radius + 1).min(x_size);
+
+            let window_sum = sums[y_max][x_max] - sums[y_min][x_max] - sums[y_max][x_min] + sums[y_min][x_min];
+            let window_size = ((y_max - y_min) * (x_max - x_min)) as f64;
+
+            x_axis_values.push((window_sum / window_size) as f32);
+        }
+        smoothed_layer.push(x_axis_values);
+    }
+
+    smoothed_layer
+}
@@ -0,0 +1,82 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::model3d::{border_z, Model3D};
+use crate::types::Axis;
+
+impl Model3D {
+    /// Exports every border as ESRI ASCII Grid to file {name}_bo{border_num}.asc
+    /// Grid is georeferenced by first edges and cell size, so axis_x and axis_y must be uniform with
+    /// the same step (square cells), non-square cells (dx and dy keys) are supported only by GDAL.
+    /// Rows are written from last y block to first one as format requires, values are border depths
+    /// (axis_z edges if model has Z axis, otherwise border values)
+    pub fn export_asc(&self, name: &str) -> Result<(), io::Error> {
+        let axis_x = self.params.axis_x();
+        let axis_y = self.params.axis_y();
+
+        let cell_size = match (uniform_step(&axis_x), uniform_step(&axis_y)) {
+            (Some(step_x), Some(step_y)) if step_x == step_y => Axis::from_fixed_units(step_x),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "ESRI ASCII Grid requires uniform X and Y axes with the same step")),
+        };
+
+        let axis_z = self.params.axis_z();
+        for border_num in 0..self.borders.borders_count() {
+            let mut file = BufWriter::new(File::create(format!("{name}_bo{border_num}.asc"))?);
+
+            writeln!(file, "ncols {}", axis_x.blocks_count())?;
+            writeln!(file, "nrows {}", axis_y.blocks_count())?;
+            writeln!(file, "xllcorner {}", axis_x.start())?;
+            writeln!(file, "yllcorner {}", axis_y.start())?;
+            writeln!(file, "cellsize {cell_size}")?;
+
+            for y_row in self.borders.rows(border_num).rev() {
+                let mut values = y_row.iter().map(|value| border_z(*value, axis_z.as_deref()));
+                if let Some(value) = values.next() {
+                    write!(file, "{value}")?;
+                }
+                for value in values {
+                    write!(file, " {value}")?;
+                }
+                writeln!(file)?;
+            }
+
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Exports every border as XYZ point cloud to file {name}_bo{border_num}.xyz
+    /// Every line is "x y z" for one block, where x and y are blocks centers and z is border depth
+    /// (axis_z edge if model has Z axis, otherwise border value)
+    pub fn export_xyz(&self, name: &str) -> Result<(), io::Error> {
+        let centers_x = self.params.axis_x().blocks_centers().clone();
+        let centers_y = self.params.axis_y().blocks_centers().clone();
+        let axis_z = self.params.axis_z();
+
+        for border_num in 0..self.borders.borders_count() {
+            let mut file = BufWriter::new(File::create(format!("{name}_bo{border_num}.xyz"))?);
+
+            for (y_row, y_center) in self.borders.rows(border_num).zip(centers_y.iter()) {
+                for (value, x_center) in y_row.iter().zip(centers_x.iter()) {
+                    writeln!(file, "{x_center} {y_center} {}", border_z(*value, axis_z.as_deref()))?;
+                }
+            }
+
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+// Function to get step of axis in fixed-point units if all its blocks have the same size
+fn uniform_step(axis: &Axis) -> Option<i64> {
+    if let Some(step) = axis.step_fixed() {
+        return Some(step)
+    }
+
+    let edges = axis.edges_fixed();
+    let step = edges[1] - edges[0];
+    edges.windows(2).all(|edges| edges[1] - edges[0] == step).then_some(step)
+}
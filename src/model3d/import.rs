@@ -0,0 +1,311 @@
+use std::path::Path;
+use std::str::Chars;
+use std::sync::Arc;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, DeJsonTok};
+
+use crate::error::GruntError;
+use crate::model3d::{BorderGrid, Model3D};
+use crate::model3d::borders3d::{border_rules, validate_layer, validate_order};
+use crate::types::{Axis, BordersOrder, BordersParams, BorderType, Params3D};
+
+// Possible errors during model import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelImportError {
+    EmptyModel,
+    BordersCount { expected: usize, found: usize },
+    RowsCount { border_id: usize, expected: usize, found: usize },
+    RowLength { border_id: usize, y: usize, expected: usize, found: usize },
+    FillValuesSize { layer_id: usize },
+}
+
+impl std::fmt::Display for ModelImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ModelImportError::EmptyModel => write!(f, "Model file contains neither params nor borders"),
+            ModelImportError::BordersCount { expected, found } =>
+                write!(f, "Model must contain {expected} borders, found {found}"),
+            ModelImportError::RowsCount { border_id, expected, found } =>
+                write!(f, "Border {border_id} must contain {expected} rows (Y axis), found {found}"),
+            ModelImportError::RowLength { border_id, y, expected, found } =>
+                write!(f, "Border {border_id}, row y - {y} must contain {expected} values (X axis), found {found}"),
+            ModelImportError::FillValuesSize { layer_id } =>
+                write!(f, "Fill values of layer {layer_id} don't match axes or layer doesn't exist"),
+        }
+    }
+}
+
+impl std::error::Error for ModelImportError {}
+
+impl Model3D {
+    /// Imports model saved by export_model, both params and borders may be saved as null.
+    /// If params are absent they are rebuilt from borders: axes with step 1 starting at 0 and
+    /// borders limits equal to min and max values of every border.
+    /// If borders are absent model has no borders. Fill values are imported if they were saved,
+    /// voxels and statistics are skipped. Seed is restored from metadata if it was saved, otherwise
+    /// imported model has no seed.
+    pub fn import_model<P: AsRef<Path>>(path: P) -> Result<Model3D, GruntError> {
+        let model_json = std::fs::read_to_string(path)?;
+        let model_json = ModelJson::deserialize_json(&model_json)?;
+
+        let fill_values = model_json.fill_values.map(|fill_values| fill_values.0).unwrap_or_default();
+        let (params, borders) = match (model_json.params, model_json.borders) {
+            (Some(params), borders) => (Arc::new(params), borders.map(|borders| borders.0).unwrap_or_default()),
+            (None, Some(borders)) => (params_from_borders(&borders.0)?, borders.0),
+            (None, None) => return Err(ModelImportError::EmptyModel.into()),
+        };
+
+        if !borders.is_empty() {
+            check_borders_sizes(&params, &borders)?;
+        }
+        let borders = BorderGrid::from_nested(&borders).unwrap_or_default();
+        if !fill_values.is_empty() {
+            check_fill_values_sizes(&params, &fill_values)?;
+        }
+
+        Ok(Model3D {
+            params,
+            seed: model_json.seed,
+            borders,
+            fill_values,
+        })
+    }
+}
+
+impl Model3D {
+    /// Checks that borders and fill values match params and every border is inside its limits
+    /// and max step, ordered borders must be at least min thickness below previous ones. Borders of models with faults are checked only for sizes, because faults
+    /// move borders out of their limits
+    pub fn validate(&self) -> Result<(), GruntError> {
+        if !self.borders.is_empty() {
+            check_grid_sizes(&self.params, &self.borders)?;
+
+            if self.params.faults().is_empty() {
+                let borders_params = self.params.borders();
+                let axes_sizes = (self.borders.y_size(), self.borders.x_size());
+                for (border_id, border) in self.borders.borders().enumerate() {
+                    let (limits, step) = border_rules(&borders_params, border_id);
+                    validate_layer(border_id, border, axes_sizes, limits, step, None)?;
+                }
+
+                if let BordersOrder::Ordered(min_thickness) = borders_params.borders_order() {
+                    for (pr_border, (border_id, border)) in self.borders.borders().zip(self.borders.borders().enumerate().skip(1)) {
+                        validate_order(border_id, border, pr_border, axes_sizes.1, min_thickness)?;
+                    }
+                }
+            }
+        }
+
+        if !self.fill_values.is_empty() {
+            check_fill_values_sizes(&self.params, &self.fill_values)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Function to create params which describe borders without saved params
+fn params_from_borders(borders: &[Vec<Vec<u32>>]) -> Result<Arc<Params3D>, GruntError> {
+    let ax_y_size = borders.first().map_or(0, |border| border.len());
+    let ax_x_size = borders.first().and_then(|border| border.first()).map_or(0, |y_row| y_row.len());
+
+    let axis_x = Axis::generate_axis_on_edges(0, ax_x_size as u32, Some(1))?;
+    let axis_y = Axis::generate_axis_on_edges(0, ax_y_size as u32, Some(1))?;
+
+    let borders_limits: Vec<[u32; 2]> = borders.iter().map(|border| {
+        let values = border.iter().flatten();
+        [values.clone().min().copied().unwrap_or(0), values.max().copied().unwrap_or(0)]
+    }).collect();
+    let borders_params = BordersParams::new(borders.len(), &[BorderType::Random], &borders_limits)?;
+
+    Ok(Params3D::new(Arc::new(axis_x), Arc::new(axis_y), None, Arc::new(borders_params), Arc::new(Vec::new())))
+}
+
+// Function to check that borders sizes match params
+pub(super) fn check_borders_sizes(params: &Params3D, borders: &[Vec<Vec<u32>>]) -> Result<(), ModelImportError> {
+    let ax_y_size = params.axis_y().blocks_count();
+    let ax_x_size = params.axis_x().blocks_count();
+    let number_of_borders = params.borders().number_of_borders() as usize;
+
+    if borders.len() != number_of_borders {
+        return Err(ModelImportError::BordersCount { expected: number_of_borders, found: borders.len() })
+    }
+
+    for (border_id, border) in borders.iter().enumerate() {
+        if border.len() != ax_y_size {
+            return Err(ModelImportError::RowsCount { border_id, expected: ax_y_size, found: border.len() })
+        }
+
+        for (y, y_row) in border.iter().enumerate() {
+            if y_row.len() != ax_x_size {
+                return Err(ModelImportError::RowLength { border_id, y, expected: ax_x_size, found: y_row.len() })
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Function to check that sizes of border grid match params
+fn check_grid_sizes(params: &Params3D, borders: &BorderGrid) -> Result<(), ModelImportError> {
+    let ax_y_size = params.axis_y().blocks_count();
+    let ax_x_size = params.axis_x().blocks_count();
+    let number_of_borders = params.borders().number_of_borders() as usize;
+
+    let (borders_count, y_size, x_size) = borders.shape();
+    if borders_count != number_of_borders {
+        return Err(ModelImportError::BordersCount { expected: number_of_borders, found: borders_count })
+    }
+    if y_size != ax_y_size {
+        return Err(ModelImportError::RowsCount { border_id: 0, expected: ax_y_size, found: y_size })
+    }
+    if x_size != ax_x_size {
+        return Err(ModelImportError::RowLength { border_id: 0, y: 0, expected: ax_x_size, found: x_size })
+    }
+
+    Ok(())
+}
+
+// Function to check that fill values exist for every layer and match axes
+fn check_fill_values_sizes(params: &Params3D, fill_values: &[Vec<Vec<f32>>]) -> Result<(), ModelImportError> {
+    let ax_y_size = params.axis_y().blocks_count();
+    let ax_x_size = params.axis_x().blocks_count();
+    let number_of_layers = params.borders().number_of_borders() as usize + 1;
+
+    if fill_values.len() != number_of_layers {
+        return Err(ModelImportError::FillValuesSize { layer_id: fill_values.len().min(number_of_layers) })
+    }
+
+    for (layer_id, layer) in fill_values.iter().enumerate() {
+        if layer.len() != ax_y_size || layer.iter().any(|y_row| y_row.len() != ax_x_size) {
+            return Err(ModelImportError::FillValuesSize { layer_id })
+        }
+    }
+
+    Ok(())
+}
+
+// Model as it is stored by export_model: {"params3D":...,"borders":[{"bo0":[{"y0":[...]}]}],...}
+struct ModelJson {
+    params: Option<Params3D>,
+    borders: Option<BordersJson>,
+    fill_values: Option<FillValuesJson>,
+    seed: Option<u64>,
+}
+
+impl DeJson for ModelJson {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<ModelJson, DeJsonErr> {
+        let mut model_json = ModelJson { params: None, borders: None, fill_values: None, seed: None };
+
+        state.curly_open(input)?;
+        while state.tok != DeJsonTok::CurlyClose {
+            let key = String::de_json(state, input)?;
+            state.colon(input)?;
+            match key.as_str() {
+                "params3D" => model_json.params = DeJson::de_json(state, input)?,
+                "borders" => model_json.borders = DeJson::de_json(state, input)?,
+                "fill_values" => model_json.fill_values = DeJson::de_json(state, input)?,
+                "metadata" => model_json.seed = MetadataJson::de_json(state, input)?.seed,
+                "voxels" | "statistics" => state.whole_field(input)?,
+                _ => return Err(state.err_exp(&key)),
+            }
+            state.eat_comma_curly(input)?;
+        }
+        state.curly_close(input)?;
+
+        Ok(model_json)
+    }
+}
+
+// Metadata stored as {"version":..,"seed":..,"index_base":..,"float_precision":..}, only seed is used
+struct MetadataJson {
+    seed: Option<u64>,
+}
+
+impl DeJson for MetadataJson {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<MetadataJson, DeJsonErr> {
+        let mut metadata_json = MetadataJson { seed: None };
+
+        state.curly_open(input)?;
+        while state.tok != DeJsonTok::CurlyClose {
+            let key = String::de_json(state, input)?;
+            state.colon(input)?;
+            match key.as_str() {
+                "seed" => metadata_json.seed = DeJson::de_json(state, input)?,
+                _ => state.whole_field(input)?,
+            }
+            state.eat_comma_curly(input)?;
+        }
+        state.curly_close(input)?;
+
+        Ok(metadata_json)
+    }
+}
+
+// Borders stored as [{"bo0":[{"y0":[...]}, ...]}, ...], first index may be any
+struct BordersJson(Vec<Vec<Vec<u32>>>);
+
+impl DeJson for BordersJson {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<BordersJson, DeJsonErr> {
+        let borders: Vec<PlaneJson<u32>> = grid_json(state, input, "bo")?;
+        Ok(BordersJson(borders.into_iter().map(|border| border.0).collect()))
+    }
+}
+
+// Fill values stored as [{"la0":[{"y0":[...]}, ...]}, ...], first index may be any
+struct FillValuesJson(Vec<Vec<Vec<f32>>>);
+
+impl DeJson for FillValuesJson {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<FillValuesJson, DeJsonErr> {
+        let layers: Vec<PlaneJson<f32>> = grid_json(state, input, "la")?;
+        Ok(FillValuesJson(layers.into_iter().map(|layer| layer.0).collect()))
+    }
+}
+
+// One border or layer stored as [{"y0":[...]}, {"y1":[...]}, ...]
+struct PlaneJson<T>(Vec<Vec<T>>);
+
+impl<T: DeJson> DeJson for PlaneJson<T> {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<PlaneJson<T>, DeJsonErr> {
+        Ok(PlaneJson(grid_json(state, input, "y")?))
+    }
+}
+
+// Function to parse array of objects with single key "<prefix><index base + id>"
+fn grid_json<T: DeJson>(state: &mut DeJsonState, input: &mut Chars, prefix: &str) -> Result<Vec<T>, DeJsonErr> {
+    let mut values: Vec<T> = Vec::new();
+    let mut index_base: Option<usize> = None;
+
+    state.block_open(input)?;
+    while state.tok != DeJsonTok::BlockClose {
+        values.push(keyed_value(state, input, prefix, values.len(), &mut index_base)?);
+        state.eat_comma_block(input)?;
+    }
+    state.block_close(input)?;
+
+    Ok(values)
+}
+
+// Function to parse object with single key "<prefix><index base + id>" and return its value,
+// index base is taken from the first key
+fn keyed_value<T: DeJson>(
+    state: &mut DeJsonState,
+    input: &mut Chars,
+    prefix: &str,
+    id: usize,
+    index_base: &mut Option<usize>,
+) -> Result<T, DeJsonErr> {
+    state.curly_open(input)?;
+
+    let key = String::de_json(state, input)?;
+    let index = key.strip_prefix(prefix).and_then(|index| index.parse::<usize>().ok());
+    let index_base = *index_base.get_or_insert(index.unwrap_or(0));
+    if index != Some(index_base + id) {
+        return Err(state.err_exp(&key))
+    }
+    state.colon(input)?;
+    let value = T::de_json(state, input)?;
+
+    state.curly_close(input)?;
+    Ok(value)
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::model3d::{BorderGrid, Model3D};
+use crate::types::Axis;
+
+/// Method to carry values of blocks to new axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Value of block with the nearest center
+    Nearest,
+    /// Bilinear interpolation between four nearest centers
+    Linear,
+}
+
+impl Model3D {
+    /// Returns model on new horizontal axes, borders and fill values are carried over with
+    /// interpolation, blocks out of old axes take values of the nearest blocks.
+    /// Borders are rounded to the nearest edge, resampled model has no seed
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use grunt::{generate_model3d_seeded, Interpolation, Params3D};
+    /// let model = generate_model3d_seeded(Arc::new(Params3D::default()), 1).unwrap();
+    /// let axis_x = Arc::new(model.params().axis_x().refine(2).unwrap());
+    /// let resampled = model.resample(axis_x, model.params().axis_y(), Interpolation::Nearest);
+    /// assert_eq!(resampled.borders().get(0, 0, 1), model.borders().get(0, 0, 0));
+    /// ```
+    pub fn resample(&self, new_axis_x: Arc<Axis>, new_axis_y: Arc<Axis>, interpolation: Interpolation) -> Model3D {
+        let weights_x = axis_weights(&self.params.axis_x(), &new_axis_x, interpolation);
+        let weights_y = axis_weights(&self.params.axis_y(), &new_axis_y, interpolation);
+
+        let mut borders = BorderGrid::new(self.borders.borders_count(), weights_y.len(), weights_x.len());
+        for border_id in 0..borders.borders_count() {
+            let old_border = self.borders.border(border_id);
+            let old_x_size = self.borders.x_size();
+            for (now_y, y_weights) in weights_y.iter().enumerate() {
+                for (now_x, x_weights) in weights_x.iter().enumerate() {
+                    let value = interpolate(y_weights, x_weights, |y, x| old_border[y * old_x_size + x] as f64);
+                    borders[(border_id, now_y, now_x)] = value.round() as u32;
+                }
+            }
+        }
+
+        let fill_values = self.fill_values.iter().map(|layer| {
+            weights_y.iter().map(|y_weights| {
+                weights_x.iter().map(|x_weights| {
+                    interpolate(y_weights, x_weights, |y, x| layer[y][x] as f64) as f32
+                }).collect()
+            }).collect()
+        }).collect();
+
+        Model3D {
+            params: self.params.with_axes(new_axis_x, new_axis_y),
+            seed: None,
+            borders,
+            fill_values,
+        }
+    }
+}
+
+// Function to find weights of old blocks for every new block, nearest block gets full weight
+fn axis_weights(old_axis: &Axis, new_axis: &Axis, interpolation: Interpolation) -> Vec<[(usize, f64); 2]> {
+    let weights = old_axis.resample(new_axis);
+    match interpolation {
+        Interpolation::Linear => weights,
+        Interpolation::Nearest => weights.into_iter().map(|[pr, next]| {
+            let nearest_id = if next.1 > pr.1 { next.0 } else { pr.0 };
+            [(nearest_id, 1.0), (nearest_id, 0.0)]
+        }).collect(),
+    }
+}
+
+fn interpolate(y_weights: &[(usize, f64); 2], x_weights: &[(usize, f64); 2], value: impl Fn(usize, usize) -> f64) -> f64 {
+    let mut result = 0.0;
+    for (y, y_weight) in y_weights {
+        for (x, x_weight) in x_weights {
+            if y_weight * x_weight != 0.0 {
+                result += y_weight * x_weight * value(*y, *x);
+            }
+        }
+    }
+
+    result
+}
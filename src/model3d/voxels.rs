@@ -0,0 +1,57 @@
+use crate::model3d::Model3D;
+
+impl Model3D {
+    /// Returns model as voxel volume z -> y -> x, where every voxel stores index of its layer
+    /// Voxels are sampled at axis_z blocks centers, border with value n lies on n edge of axis_z,
+    /// so voxel belongs to layer m if m borders are above its center.
+    /// Returns None if model has no Z axis
+    pub fn to_voxels(&self) -> Option<Vec<Vec<Vec<u8>>>> {
+        Some(self.layer_ids(self.params.axis_z()?.blocks_count()))
+    }
+
+    // Function to split ax_z_size blocks in every column by borders
+    pub(super) fn layer_ids(&self, ax_z_size: usize) -> Vec<Vec<Vec<u8>>> {
+        let ax_y_size = self.params.axis_y().blocks_count();
+        let ax_x_size = self.params.axis_x().blocks_count();
+
+        let mut voxels = vec![vec![vec![0u8; ax_x_size]; ax_y_size]; ax_z_size];
+        let mut now_borders: Vec<u32> = Vec::with_capacity(self.borders.borders_count());
+
+        for now_y in 0..ax_y_size {
+            for now_x in 0..ax_x_size {
+                now_borders.clear();
+                now_borders.extend(self.borders.borders().map(|border| border[now_y * ax_x_size + now_x]));
+                now_borders.sort_unstable();
+
+                // Borders above center of voxel z are borders with values <= z
+                let mut now_layer = 0;
+                for (now_z, z_slice) in voxels.iter_mut().enumerate() {
+                    while now_layer < now_borders.len() && now_borders[now_layer] as usize <= now_z {
+                        now_layer += 1;
+                    }
+                    z_slice[now_y][now_x] = now_layer as u8;
+                }
+            }
+        }
+
+        voxels
+    }
+
+    /// Returns model as voxel volume z -> y -> x, where every voxel stores fill value of its layer
+    /// Returns None if model has no Z axis or no fill values
+    pub fn to_voxels_filled(&self) -> Option<Vec<Vec<Vec<f32>>>> {
+        if self.fill_values.is_empty() {
+            return None
+        }
+
+        let voxels = self.to_voxels()?;
+
+        Some(voxels.iter().map(|z_slice| {
+            z_slice.iter().enumerate().map(|(now_y, y_row)| {
+                y_row.iter().enumerate().map(|(now_x, layer)| {
+                    self.fill_values[*layer as usize][now_y][now_x]
+                }).collect()
+            }).collect()
+        }).collect())
+    }
+}
@@ -0,0 +1,104 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::model3d::{border_z, Model3D};
+
+impl Model3D {
+    /// Exports model to legacy VTK file {name}.vtk as RECTILINEAR_GRID built on axes edges,
+    /// so non-uniform axes are kept as they are.
+    /// If model has no Z axis, Z coordinates are 0..=max border value + 1 with step 1.
+    /// Cell data: "layer_id" and "fill_value" (if model has fill values).
+    /// Point data: "border_{n}" for every border, it is Z coordinate of point minus depth of border
+    /// (mean of neighbour blocks), so border surface is contour with value 0
+    pub fn export_vtk(&self, name: &str) -> Result<(), io::Error> {
+        let mut file = BufWriter::new(File::create(format!("{name}.vtk"))?);
+
+        let edges_x = self.params.axis_x().blocks_edges().clone();
+        let edges_y = self.params.axis_y().blocks_edges().clone();
+        let axis_z = self.params.axis_z();
+        let edges_z: Vec<f64> = match axis_z.as_deref() {
+            Some(axis_z) => axis_z.blocks_edges().clone(),
+            None => {
+                let max_value = self.borders.iter().max().copied().unwrap_or(0);
+                (0..=max_value + 1).map(|value| value as f64).collect()
+            },
+        };
+
+        writeln!(file, "# vtk DataFile Version 3.0")?;
+        writeln!(file, "grunt model")?;
+        writeln!(file, "ASCII")?;
+        writeln!(file, "DATASET RECTILINEAR_GRID")?;
+        writeln!(file, "DIMENSIONS {} {} {}", edges_x.len(), edges_y.len(), edges_z.len())?;
+        for (axis_name, edges) in [("X", &edges_x), ("Y", &edges_y), ("Z", &edges_z)] {
+            writeln!(file, "{axis_name}_COORDINATES {} double", edges.len())?;
+            write_values(&mut file, edges.iter())?;
+        }
+
+        let layers = self.layer_ids(edges_z.len() - 1);
+        writeln!(file, "CELL_DATA {}", (edges_x.len() - 1) * (edges_y.len() - 1) * (edges_z.len() - 1))?;
+        writeln!(file, "SCALARS layer_id int 1")?;
+        writeln!(file, "LOOKUP_TABLE default")?;
+        write_values(&mut file, layers.iter().flatten().flatten())?;
+
+        if !self.fill_values.is_empty() {
+            writeln!(file, "SCALARS fill_value float 1")?;
+            writeln!(file, "LOOKUP_TABLE default")?;
+            let fill_values = layers.iter().flat_map(|z_slice| {
+                z_slice.iter().enumerate().flat_map(move |(now_y, y_row)| {
+                    y_row.iter().enumerate().map(move |(now_x, layer)| self.fill_values[*layer as usize][now_y][now_x])
+                })
+            });
+            write_values(&mut file, fill_values)?;
+        }
+
+        if !self.borders.is_empty() {
+            writeln!(file, "POINT_DATA {}", edges_x.len() * edges_y.len() * edges_z.len())?;
+        }
+        for border_num in 0..self.borders.borders_count() {
+            let border_depths: Vec<Vec<f64>> = self.borders.rows(border_num).map(|y_row| {
+                y_row.iter().map(|value| border_z(*value, axis_z.as_deref())).collect()
+            }).collect();
+            let point_depths = points_from_blocks(&border_depths);
+
+            writeln!(file, "SCALARS border_{border_num} double 1")?;
+            writeln!(file, "LOOKUP_TABLE default")?;
+            let distances = edges_z.iter().flat_map(|z_edge| {
+                point_depths.iter().flatten().map(move |depth| z_edge - depth)
+            });
+            write_values(&mut file, distances)?;
+        }
+
+        file.flush()
+    }
+}
+
+// Function to get values in grid points as mean of values in blocks around them
+fn points_from_blocks(blocks: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let ax_y_size = blocks.len();
+    let ax_x_size = blocks[0].len();
+
+    (0..=ax_y_size).map(|now_y| {
+        let y_range = now_y.saturating_sub(1)..now_y.min(ax_y_size - 1) + 1;
+        (0..=ax_x_size).map(|now_x| {
+            let x_range = now_x.saturating_sub(1)..now_x.min(ax_x_size - 1) + 1;
+            let neighbours: Vec<f64> = blocks[y_range.clone()].iter()
+                .flat_map(|y_row| y_row[x_range.clone()].iter().copied())
+                .collect();
+            neighbours.iter().sum::<f64>() / neighbours.len() as f64
+        }).collect()
+    }).collect()
+}
+
+// Function to write values, 9 values per line
+fn write_values<W: Write, T: std::fmt::Display>(
+    file: &mut W,
+    values: impl Iterator<Item = T>,
+) -> Result<(), io::Error> {
+    for (value_num, value) in values.enumerate() {
+        if value_num != 0 {
+            file.write_all(if value_num % 9 == 0 { b"\n" } else { b" " })?;
+        }
+        write!(file, "{value}")?;
+    }
+    writeln!(file)
+}
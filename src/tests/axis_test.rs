@@ -1,4 +1,4 @@
-use super::types::Axis;
+use super::types::{Axis, AxisError};
 
 // Some static tests for axis generation to check/find float operation problems
 #[test]
@@ -81,7 +81,7 @@ fn axis_from_vec_tests() {
     let vec = vec![1.0, 1.00255];
     let ax = Axis::from_vec_as_edges(&vec).unwrap();
     assert_eq!(*ax.blocks_edges(), vec![1.0, 1.003]);
-    assert_eq!(*ax.blocks_centers(), vec![1.002]);
+    assert_eq!(*ax.blocks_centers(), vec![1.0015]);
 
     let vec = vec![1, 3, 4, 10];
     let ax = Axis::from_vec_as_edges(&vec).unwrap();
@@ -108,3 +108,105 @@ fn axis_from_vec_tests() {
     assert_eq!(*ax.blocks_edges(), vec![0.0, 2.0, 3.5, 7.0, 13.0]);
     assert_eq!(*ax.blocks_centers(), vec![1.0, 3.0, 4.0, 10.0]);
 }
+
+// Fixed-point values must stay exact far away from zero and for steps with odd number of millis
+#[test]
+fn axis_fixed_point_tests() {
+    let ax = Axis::generate_axis_on_edges(1.0, 1.003, Some(0.001)).unwrap();
+    assert_eq!(*ax.blocks_centers(), vec![1.0005, 1.0015, 1.0025]);
+
+    let ax = Axis::generate_axis_on_centers(1.0, 1.002, Some(0.001)).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![0.9995, 1.0005, 1.0015, 1.0025]);
+
+    let ax = Axis::generate_axis_on_edges(1_000_000_000.0, 1_000_000_100.0, Some(0.001)).unwrap();
+    assert_eq!(ax.blocks_count(), 100_000);
+    assert_eq!(ax.blocks_edges()[99_999], 1_000_000_099.999);
+    assert_eq!(ax.blocks_centers()[99_999], 1_000_000_099.999_5);
+    assert_eq!(ax.end(), 1_000_000_100.0);
+
+    let ax = Axis::generate_axis_on_edges(-5_000_000.0, 5_000_000.0, Some(0.3)).unwrap();
+    assert_eq!(ax.edges_fixed()[1] - ax.edges_fixed()[0], 3000);
+    assert_eq!(ax.end_fixed(), Axis::to_fixed(4_999_999.9).unwrap());
+
+    let ax = Axis::from_vec_as_edges(&[-20000.0, -19999.997, -19999.994]).unwrap();
+    assert_eq!(*ax.blocks_centers(), vec![-19999.9985, -19999.9955]);
+    assert_eq!(ax.centers_fixed(), &[-199_999_985, -199_999_955]);
+
+    let ax = Axis::from_vec_as_centers(&[0.0, 0.003, 0.01]).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![-0.0015, 0.0015, 0.0065, 0.0135]);
+}
+
+// Lookup of blocks by coordinates
+#[test]
+fn axis_lookup_tests() {
+    let ax = Axis::generate_axis_on_edges(-10.0, 10.0, Some(0.5)).unwrap();
+    assert_eq!(ax.locate(-10), Some(0));
+    assert_eq!(ax.locate(-9.5), Some(1));
+    assert_eq!(ax.locate(9.9999), Some(39));
+    assert_eq!(ax.locate(10.0), Some(39));
+    assert_eq!(ax.locate(10.001), None);
+
+    let ax = Axis::from_vec_as_centers(&[1, 3, 4, 10]).unwrap();
+    for (coord, block_id) in [(0.0, 0), (1.999, 0), (2.0, 1), (3.6, 2), (12.5, 3), (13.0, 3)] {
+        assert_eq!(ax.locate(coord), Some(block_id));
+    }
+    assert_eq!(ax.locate(13.5), None);
+
+    assert_eq!(ax.interp_weights(3.0), Some([(1, 1.0), (2, 0.0)]));
+    assert_eq!(ax.interp_weights(7.0), Some([(2, 0.5), (3, 0.5)]));
+    assert_eq!(ax.interp_weights(11.0), Some([(3, 1.0), (3, 0.0)]));
+    assert_eq!(ax.interp_weights(-1), None);
+
+    assert_eq!(ax.blocks_in(3, 10), 1..4);
+    assert_eq!(ax.blocks_in(3.5, 3.9), 2..2);
+    assert!(ax.blocks_in(5, 2).is_empty());
+}
+
+// Geometric and logarithmic axes must be rounded and validated as uniform ones
+#[test]
+fn axis_non_uniform_generation_tests() {
+    let ax = Axis::generate_geometric(-1.0, 1.0, 0.5, 1.5, 5.0).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![-1.0, -0.5, 0.0, 0.5, 1.0, 1.75, 2.875, 4.563]);
+    assert_eq!(ax.blocks_centers()[4], 1.375);
+
+    let minimal_step = AxisError::MinimalStep { index: 1, prev: 0.0, value: 0.001 };
+    assert_eq!(Axis::generate_geometric(0.0, 0.0, 0.001, 1.1, 0.01).err(), Some(minimal_step));
+
+    let ax = Axis::generate_geometric(0.0, 0.0, 0.0024, 1.1, 0.01).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![0.0, 0.003, 0.006, 0.009]);
+
+    let ax = Axis::generate_geometric(0.0, 1.0, 0.5, 1e300, 1e12).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![0.0, 0.5, 1.0]);
+
+    assert_eq!(Axis::generate_geometric(0.0, 2.0, 1.0, 0.9, 5.0).err(), Some(AxisError::InvalidGrowth { growth: 0.9 }));
+    assert_eq!(Axis::generate_geometric(0.0, 6.0, 1.0, 1.2, 5.0).err(), Some(AxisError::InvalidRange { start: 6.0, end: 5.0 }));
+    assert_eq!(Axis::generate_geometric(0.0, 2.0, 0.0001, 1.2, 5.0).err(), Some(AxisError::TooSmallStep { step: 0.0 }));
+    assert!(matches!(Axis::generate_geometric(0.0, 0.0, 3.0, 1.2, 2.0), Err(AxisError::NotEnoughElements)));
+
+    let ax = Axis::generate_logarithmic(0.1, 100.0, 6).unwrap();
+    assert_eq!(*ax.blocks_edges(), vec![0.1, 0.316, 1.0, 3.162, 10.0, 31.623, 100.0]);
+
+    assert_eq!(Axis::generate_logarithmic(0.0, 10.0, 3).err(), Some(AxisError::NotPositiveStart { start: 0.0 }));
+    assert_eq!(Axis::generate_logarithmic(5.0, 1.0, 3).err(), Some(AxisError::InvalidRange { start: 5.0, end: 1.0 }));
+    assert_eq!(Axis::generate_logarithmic(1.0, 1.01, 10).err(), Some(AxisError::MinimalStep { index: 1, prev: 1.0, value: 1.001 }));
+}
+
+// Errors must point to the wrong value of input vec
+#[test]
+fn axis_errors_tests() {
+    let not_ordered = Axis::from_vec_as_edges(&[1.0, 2.0, 3.0, 2.5]).err();
+    assert_eq!(not_ordered, Some(AxisError::NotOrderedVec { index: 3, prev: 3.0, value: 2.5 }));
+    assert_eq!(not_ordered.unwrap().to_string(), "Values in input Vec must constanly increase, value 2.5 at 3 follows 3");
+
+    let minimal_step = Axis::from_vec_as_centers(&[1.0, 1.001, 2.0]).err();
+    assert_eq!(minimal_step, Some(AxisError::MinimalStep { index: 1, prev: 1.0, value: 1.001 }));
+
+    assert_eq!(Axis::from_vec_as_edges(&[1.0]).err(), Some(AxisError::TooSmallVec { len: 1 }));
+    assert_eq!(Axis::generate_axis_on_edges(2, 1, None).err(), Some(AxisError::InvalidRange { start: 2.0, end: 1.0 }));
+    assert_eq!(Axis::generate_axis_on_edges(0.0, 1.0, Some(0.0004)).err(), Some(AxisError::TooSmallStep { step: 0.0 }));
+
+    assert_eq!(Axis::to_fixed(1e15).err(), Some(AxisError::OutOfRange { value: 1e15 }));
+    assert!(matches!(Axis::from_vec_as_edges(&[0.0, f64::NAN]).err(), Some(AxisError::OutOfRange { .. })));
+    assert_eq!(Axis::generate_axis_on_edges(-1e15, 0.0, None).err(), Some(AxisError::OutOfRange { value: -1e15 }));
+    assert_eq!(Axis::generate_axis_on_edges(0, 10, Some(1)).unwrap().locate(1e300), None);
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, FillType, Params3D};
+use super::model3d::{generate_model3d_seeded, Model3D, ModelImportError};
+use super::error::GruntError;
+
+fn binary_test_model() -> Model3D {
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(0, 9, Some(1)).unwrap());
+    let axis_y = Arc::new(Axis::from_vec_as_edges(&[0, 1, 3, 7, 15]).unwrap());
+    let borders = Arc::new(BordersParams::new(3, &[BorderType::Random], &[[5, 20], [30, 40]]).unwrap());
+    let fill_values = FillValues::new(vec![FillType::RandomBetween(-10, 10)], 0, true).unwrap();
+    let params = Params3D::new(axis_x, axis_y, None, borders, Arc::new(vec![fill_values]));
+
+    generate_model3d_seeded(params, 11).unwrap()
+}
+
+fn assert_same_models(first: &Model3D, second: &Model3D) {
+    assert_eq!(first.params().axis_y().blocks_edges(), second.params().axis_y().blocks_edges());
+    assert_eq!(first.seed(), second.seed());
+    assert_eq!(first.borders(), second.borders());
+    assert_eq!(first.fill_values(), second.fill_values());
+}
+
+// Model written in binary format must be read back without changes
+#[test]
+fn binary_round_trip_tests() {
+    let model = binary_test_model();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    model.write_binary(&mut buffer, false).unwrap();
+    assert_eq!(&buffer[..4], b"GRNT");
+    assert_same_models(&model, &Model3D::read_binary(buffer.as_slice()).unwrap());
+
+    let path = std::env::temp_dir().join("grunt_binary_test");
+    model.export_binary(path.to_str().unwrap(), false).unwrap();
+    let path = path.with_extension("grunt");
+    let imported = Model3D::import_binary(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_same_models(&model, &imported);
+
+    assert!(Model3D::read_binary(&buffer[..buffer.len() - 1]).is_err());
+    assert!(Model3D::read_binary(&b"JSON{}"[..]).is_err());
+}
+
+// Sizes from malformed file must be checked with params before values are read
+#[test]
+fn binary_malformed_tests() {
+    let mut buffer: Vec<u8> = Vec::new();
+    binary_test_model().write_binary(&mut buffer, false).unwrap();
+    let params_len = u32::from_le_bytes(buffer[8..12].try_into().unwrap()) as usize;
+    let borders_sizes = 12 + params_len + 9;
+
+    let mut huge_params = buffer.clone();
+    huge_params[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(Model3D::read_binary(huge_params.as_slice()), Err(GruntError::Io(_))));
+
+    let mut huge_borders = buffer.clone();
+    huge_borders[borders_sizes..borders_sizes + 12].copy_from_slice(&[u8::MAX; 12]);
+    let error = Model3D::read_binary(huge_borders.as_slice()).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::BordersCount { expected: 3, found: 4_294_967_295 })));
+
+    let mut wrong_rows = buffer.clone();
+    wrong_rows[borders_sizes + 4..borders_sizes + 8].copy_from_slice(&5u32.to_le_bytes());
+    let error = Model3D::read_binary(wrong_rows.as_slice()).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::RowsCount { border_id: 0, expected: 4, found: 5 })));
+
+    let mut empty_huge_rows = buffer.clone();
+    empty_huge_rows[borders_sizes..borders_sizes + 12].copy_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0, 255, 255, 255, 255]);
+    let error = Model3D::read_binary(empty_huge_rows.as_slice()).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::RowsCount { border_id: 0, expected: 4, found: 1 })));
+
+    let fill_values_sizes = borders_sizes + 12 + 3 * 4 * 9 * 4;
+    let mut empty_huge_layers = buffer[..fill_values_sizes].to_vec();
+    empty_huge_layers.extend_from_slice(&[0, 0, 0, 0, 4, 0, 0, 0, 255, 255, 255, 255]);
+    let error = Model3D::read_binary(empty_huge_layers.as_slice()).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::FillValuesSize { layer_id: 0 })));
+
+    let mut wrong_layers = buffer.clone();
+    wrong_layers[fill_values_sizes..fill_values_sizes + 4].copy_from_slice(&2u32.to_le_bytes());
+    let error = Model3D::read_binary(wrong_layers.as_slice()).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::FillValuesSize { layer_id: 2 })));
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn binary_compressed_round_trip_tests() {
+    let model = binary_test_model();
+
+    let mut plain: Vec<u8> = Vec::new();
+    model.write_binary(&mut plain, false).unwrap();
+    let mut compressed: Vec<u8> = Vec::new();
+    model.write_binary(&mut compressed, true).unwrap();
+
+    assert!(compressed.len() < plain.len());
+    assert_same_models(&model, &Model3D::read_binary(compressed.as_slice()).unwrap());
+}
+
+#[cfg(not(feature = "compression"))]
+#[test]
+fn binary_compression_unsupported_tests() {
+    let mut buffer: Vec<u8> = Vec::new();
+    assert!(binary_test_model().write_binary(&mut buffer, true).is_err());
+}
@@ -0,0 +1,37 @@
+use super::model3d::BorderGrid;
+
+// Values must be stored as border -> y -> x and be available by index, rows and borders
+#[test]
+fn border_grid_tests() {
+    let grid = BorderGrid::from_vec(2, 2, 3, (0..12).collect()).unwrap();
+
+    assert_eq!(grid.shape(), (2, 2, 3));
+    assert_eq!(grid[(1, 0, 2)], 8);
+    assert_eq!(grid.get(1, 1, 2), Some(11));
+    assert_eq!(grid.get(1, 2, 0), None);
+    assert_eq!(grid.row(0, 1), &[3, 4, 5]);
+    assert_eq!(grid.border(1), &[6, 7, 8, 9, 10, 11]);
+    assert_eq!(grid.rows(1).last(), Some(&[9, 10, 11][..]));
+    assert_eq!(grid.borders().count(), 2);
+    assert_eq!(grid.iter().sum::<u32>(), 66);
+
+    assert_eq!(BorderGrid::from_nested(&grid.to_nested()), Some(grid.clone()));
+    assert!(BorderGrid::from_vec(2, 2, 2, vec![0; 7]).is_none());
+
+    let mut grid = grid;
+    grid.row_mut(0, 0).fill(1);
+    grid[(1, 1, 1)] = 0;
+    assert_eq!(grid.border(0), &[1, 1, 1, 3, 4, 5]);
+    assert_eq!(grid.get(1, 1, 1), Some(0));
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn border_grid_ndarray_tests() {
+    let mut grid = BorderGrid::from_vec(2, 2, 3, (0..12).collect()).unwrap();
+    assert_eq!(grid.view()[[1, 0, 2]], 8);
+    assert_eq!(grid.view().shape(), &[2, 2, 3]);
+
+    grid.view_mut()[[0, 1, 1]] = 40;
+    assert_eq!(grid[(0, 1, 1)], 40);
+}
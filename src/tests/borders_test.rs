@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, BordersOrder, CovarianceModel, FillValues, Params3D};
+use super::model3d::{generate_model3d, generate_model3d_seeded, validate_layer, BorderValidationError};
+
+// Every violation must be returned with its border id and position
+#[test]
+fn border_validation_tests() {
+    let border = [5, 6, 7, 5, 7, 9];
+    assert_eq!(validate_layer(0, &border, (2, 3), [5, 9], Some(2), None), Ok(()));
+    assert_eq!(validate_layer(1, &border, (2, 3), [5, 8], None, None), Err(BorderValidationError::OutOfLimits { layer_id: 1, y: 1, x: 2 }));
+    assert_eq!(validate_layer(2, &border, (2, 3), [0, 9], Some(1), None), Err(BorderValidationError::StepOverflowLeft { layer_id: 2, y: 1, x: 1 }));
+
+    let border = [5, 6, 8, 7];
+    assert_eq!(validate_layer(3, &border, (2, 2), [0, 9], Some(2), None), Err(BorderValidationError::StepOverflowUpper { layer_id: 3, y: 1, x: 0 }));
+    assert_eq!(validate_layer(4, &border[..2], (1, 2), [0, 9], None, None), Err(BorderValidationError::GridTooSmall { layer_id: 4 }));
+    assert_eq!(validate_layer(5, &border[..3], (2, 2), [0, 9], None, None), Err(BorderValidationError::SizeMismatch { layer_id: 5, expected: 4, found: 3 }));
+    assert_eq!(validate_layer(6, &border, (2, 2), [0, 9], None, Some(&[0; 5])), Err(BorderValidationError::SizeMismatch { layer_id: 6, expected: 4, found: 5 }));
+
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(0, 5, Some(1)).unwrap());
+    let axis_y = Arc::new(Axis::generate_axis_on_edges(0, 1, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(1, &[BorderType::Random], &[[1, 4]]).unwrap());
+    let params = Params3D::new(axis_x, axis_y, None, borders, Arc::new(vec![FillValues::default()]));
+    assert_eq!(generate_model3d(params).unwrap_err(), BorderValidationError::GridTooSmall { layer_id: 0 });
+}
+
+// Ordered borders must never cross and keep min thickness even with overlapping limits
+#[test]
+fn borders_order_tests() {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 15, Some(1)).unwrap());
+    let borders_type = [BorderType::RandomWithStep(3, 1.0), BorderType::Random];
+    let borders_limits = [[35, 89], [75, 114], [95, 129]];
+
+    let borders = BordersParams::new(3, &borders_type, &borders_limits).unwrap()
+        .with_order(BordersOrder::Ordered(4)).unwrap();
+    assert_eq!(borders.borders_order(), BordersOrder::Ordered(4));
+
+    let params = Params3D::new(axis.clone(), axis.clone(), None, Arc::new(borders), Arc::new(vec![FillValues::default()]));
+    let model = generate_model3d(params).unwrap();
+
+    for (pr_border, now_border) in model.borders().borders().zip(model.borders().borders().skip(1)) {
+        assert!(pr_border.iter().zip(now_border).all(|(pr_value, now_value)| *now_value >= pr_value + 4));
+    }
+    assert!(model.validate().is_ok());
+
+    // Every ordered border must keep its own step and limits
+    let smooth_types = [BorderType::Noise { octaves: 2, persistence: 0.5, wavelength: 6.0 }, BorderType::RandomWithStep(2, 0.5)];
+    let borders = BordersParams::new(3, &smooth_types, &borders_limits).unwrap().with_order(BordersOrder::Ordered(4)).unwrap();
+    let params = Params3D::new(axis.clone(), axis.clone(), None, Arc::new(borders), Arc::new(vec![FillValues::default()]));
+    for seed in 0..5 {
+        assert!(generate_model3d_seeded(params.clone(), seed).unwrap().validate().is_ok());
+    }
+
+    let borders = BordersParams::new(3, &borders_type, &borders_limits).unwrap();
+    assert!(borders.clone().with_order(BordersOrder::Ordered(26)).is_err());
+    assert!(borders.clone().with_order(BordersOrder::Ordered(15)).is_ok());
+    assert!(borders.with_order(BordersOrder::Free).is_ok());
+
+    let borders = BordersParams::new(4, &borders_type, &borders_limits).unwrap();
+    assert!(borders.with_order(BordersOrder::Ordered(0)).is_err());
+}
+
+// Probability of step determines how many values differ from previous ones
+#[test]
+fn random_with_step_probability_tests() {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 30, Some(1)).unwrap());
+    let count_steps = |prob: f32| {
+        let borders = Arc::new(BordersParams::new(1, &[BorderType::RandomWithStep(5, prob)], &[[0, 1000]]).unwrap());
+        let params = Params3D::new(axis.clone(), axis.clone(), None, borders, Arc::new(vec![FillValues::default()]));
+        let model = generate_model3d_seeded(params, 3).unwrap();
+
+        model.borders().rows(0).map(|y_row| y_row.windows(2).filter(|pair| pair[0] != pair[1]).count()).sum::<usize>()
+    };
+
+    assert_eq!(count_steps(0.0), 0);
+    assert!(count_steps(0.1) < count_steps(0.9));
+
+    assert!(BordersParams::new(1, &[BorderType::RandomWithStep(5, 1.5)], &[[0, 10]]).is_err());
+    assert!(BordersParams::new(1, &[BorderType::RandomWithStep(5, -0.1)], &[[0, 10]]).is_err());
+    assert!(BordersParams::new(1, &[BorderType::RandomWithStep(5, f32::NAN)], &[[0, 10]]).is_err());
+}
+
+// Noise surfaces must depend on coordinates, not on axis resolution
+#[test]
+fn noise_borders_tests() {
+    let noise = [BorderType::Noise { octaves: 3, persistence: 0.5, wavelength: 8.0 }];
+    let generate_noise = |step: f64| {
+        let axis = Arc::new(Axis::generate_axis_on_centers(0.0, 20.0, Some(step)).unwrap());
+        let borders = Arc::new(BordersParams::new(1, &noise, &[[100, 200]]).unwrap());
+        let params = Params3D::new(axis.clone(), axis, None, borders, Arc::new(vec![FillValues::default()]));
+        generate_model3d_seeded(params, 11).unwrap().borders().to_nested().remove(0)
+    };
+
+    let coarse = generate_noise(1.0);
+    let fine = generate_noise(0.5);
+    assert!(coarse.iter().flatten().all(|value| (100..=200).contains(value)));
+    assert!(coarse.iter().flatten().any(|value| *value != coarse[0][0]));
+    for (y_id, y_row) in coarse.iter().enumerate() {
+        for (x_id, value) in y_row.iter().enumerate() {
+            assert_eq!(*value, fine[y_id * 2][x_id * 2]);
+        }
+    }
+
+    let bad_noise = [BorderType::Noise { octaves: 0, persistence: 0.5, wavelength: 8.0 }];
+    assert!(BordersParams::new(1, &bad_noise, &[[0, 10]]).is_err());
+    let bad_noise = [BorderType::Noise { octaves: 2, persistence: 0.5, wavelength: -1.0 }];
+    assert!(BordersParams::new(1, &bad_noise, &[[0, 10]]).is_err());
+}
+
+// Gaussian field must have variance close to sill and be smoother for longer ranges
+#[test]
+fn gaussian_field_borders_tests() {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 64, Some(1)).unwrap());
+    let generate_field = |model: CovarianceModel, range: f32, sill: f32| {
+        let field = [BorderType::GaussianField { model, range, sill, nugget: 0.0 }];
+        let borders = Arc::new(BordersParams::new(1, &field, &[[0, 2000]]).unwrap());
+        let params = Params3D::new(axis.clone(), axis.clone(), None, borders, Arc::new(vec![FillValues::default()]));
+        generate_model3d_seeded(params, 5).unwrap().borders().to_nested().remove(0)
+    };
+    let mean_step = |border: &Vec<Vec<u32>>| {
+        border.iter().flat_map(|y_row| y_row.windows(2).map(|pair| pair[0].abs_diff(pair[1]) as f64)).sum::<f64>()
+    };
+
+    let border = generate_field(CovarianceModel::Exponential, 4.0, 400.0);
+    let values: Vec<f64> = border.iter().flatten().map(|value| *value as f64).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    assert!((200.0..600.0).contains(&variance));
+
+    let smooth = generate_field(CovarianceModel::Gaussian, 30.0, 400.0);
+    let rough = generate_field(CovarianceModel::Spherical, 2.0, 400.0);
+    assert!(mean_step(&smooth) < mean_step(&rough));
+
+    let flat = generate_field(CovarianceModel::Spherical, 10.0, 0.0);
+    assert!(flat.iter().flatten().all(|value| *value == 1000));
+
+    let bad_field = [BorderType::GaussianField { model: CovarianceModel::Gaussian, range: 0.0, sill: 1.0, nugget: 0.0 }];
+    assert!(BordersParams::new(1, &bad_field, &[[0, 10]]).is_err());
+
+    // Embedding is built on regular grid with the smallest step, so it can be too big
+    let field = [BorderType::GaussianField { model: CovarianceModel::Exponential, range: 4.0, sill: 1.0, nugget: 0.0 }];
+    let borders = Arc::new(BordersParams::new(2, &field, &[[0, 10]]).unwrap());
+    let fine_axis = Arc::new(Axis::from_vec_as_edges(&[0.0, 0.002, 0.004, 10000.0]).unwrap());
+    let params = Params3D::new(fine_axis.clone(), fine_axis, None, borders, Arc::new(vec![FillValues::default()]));
+    let error = generate_model3d_seeded(params, 0).unwrap_err();
+    assert!(matches!(error, BorderValidationError::EmbeddingTooBig { layer_id: 0, .. }));
+}
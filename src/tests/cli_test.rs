@@ -0,0 +1,76 @@
+use super::cli::{model_info, run, CliError};
+use super::model3d::{dataset_seed, Model3D};
+
+fn cli_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+// Generated models must be exported, converted and validated by commands
+#[test]
+fn cli_commands_tests() {
+    let out_dir = std::env::temp_dir().join("grunt_cli_test");
+    let out_dir_str = out_dir.to_str().unwrap();
+
+    run(&cli_args(&["generate", "--axis-x", "0,10,2", "--axis-y", "0,6", "--borders", "3", "--limits", "1:5,6:9",
+        "--border-type", "step:2:0.5", "--fill", "0:1", "--seed", "4", "--count", "2", "--out-dir", out_dir_str]))
+        .unwrap();
+
+    let first = Model3D::import_model(out_dir.join("model_3d_0.json")).unwrap();
+    let second = Model3D::import_model(out_dir.join("model_3d_1.json")).unwrap();
+    assert_eq!(first.borders().shape(), (3, 6, 5));
+    assert_eq!(first.fill_values().len(), 4);
+    assert_ne!(first.borders(), second.borders());
+    assert_eq!(second.seed(), Some(dataset_seed(4, 1)));
+
+    let model_path = out_dir.join("model_3d_1.json");
+    run(&cli_args(&["export", model_path.to_str().unwrap(), "--format", "binary", "--out-dir", out_dir_str]))
+        .unwrap();
+    let converted = Model3D::import_binary(out_dir.join("model_3d_1.grunt")).unwrap();
+    assert_eq!(converted.borders(), second.borders());
+
+    run(&cli_args(&["validate", out_dir.join("model_3d_1.grunt").to_str().unwrap()])).unwrap();
+    run(&cli_args(&["info", model_path.to_str().unwrap()])).unwrap();
+    let seed_line = format!("Seed: {}\n", dataset_seed(4, 1));
+    assert!(model_info(&Model3D::import_model(&model_path).unwrap()).starts_with(&seed_line));
+
+    let error = run(&cli_args(&["export", model_path.to_str().unwrap(), "--out-dir", out_dir_str])).unwrap_err();
+    let overwrite = CliError::OverwriteInput(model_path.to_str().unwrap().to_string());
+    assert_eq!(error.downcast_ref::<CliError>(), Some(&overwrite));
+    assert_eq!(Model3D::import_model(&model_path).unwrap().borders(), second.borders());
+
+    run(&cli_args(&["generate", "--axis-x", "0,16", "--axis-y", "0,8", "--borders", "1", "--limits", "10:30",
+        "--border-type", "gaussian:exponential:4:9:0.5", "--seed", "2", "--name", "field", "--out-dir", out_dir_str]))
+        .unwrap();
+    let field = Model3D::import_model(out_dir.join("field_0.json")).unwrap();
+    assert!(field.borders().as_slice().iter().any(|value| *value != field.borders().as_slice()[0]));
+
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+// Wrong commands and options must be reported
+#[test]
+fn cli_errors_tests() {
+    let error = run(&cli_args(&["generat"])).unwrap_err();
+    assert_eq!(error.downcast_ref::<CliError>(), Some(&CliError::UnknownCommand(String::from("generat"))));
+
+    let error = run(&cli_args(&["info", "--seed", "1"])).unwrap_err();
+    assert_eq!(error.downcast_ref::<CliError>(), Some(&CliError::UnknownFlag(String::from("--seed"))));
+
+    let error = run(&cli_args(&["generate", "--count"])).unwrap_err();
+    assert_eq!(error.downcast_ref::<CliError>(), Some(&CliError::MissingValue(String::from("--count"))));
+
+    let error = run(&cli_args(&["generate", "--limits", "5-10"])).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<CliError>(),
+        Some(&CliError::InvalidValue { flag: String::from("--limits"), value: String::from("5-10") }),
+    );
+
+    let error = run(&cli_args(&["generate", "--border-type", "gaussian:linear:4:9:0"])).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<CliError>(),
+        Some(&CliError::InvalidValue { flag: String::from("--border-type"), value: String::from("gaussian:linear:4:9:0") }),
+    );
+
+    assert!(run(&[]).is_err());
+    assert!(run(&cli_args(&["validate"])).is_err());
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use nanoserde::{DeJson, SerJson};
+
+use super::error::GruntError;
+use super::types::{Axis, BordersParams, BorderType, BordersOrder, CovarianceModel, Fault, FillValues, FillType, Params3D};
+
+// Serialized params must be loaded back without changes
+#[test]
+fn params_round_trip_tests() {
+    let axis_x = Arc::new(Axis::generate_axis_on_centers(1, 15, Some(2)).unwrap());
+    let axis_y = Arc::new(Axis::from_vec_as_edges(&[0.0, 1.5, 2.0, 4.25]).unwrap());
+    let axis_z = Arc::new(Axis::generate_axis_on_edges(0.0, 50.0, Some(0.5)).unwrap());
+    let borders_type = [
+        BorderType::RandomWithStep(3, 0.5),
+        BorderType::Noise { octaves: 2, persistence: 0.5, wavelength: 10.0 },
+        BorderType::GaussianField { model: CovarianceModel::Spherical, range: 5.0, sill: 2.0, nugget: 0.1 },
+    ];
+    let borders = BordersParams::new(3, &borders_type, &[[5, 10], [15, 20], [25, 30]]).unwrap()
+        .with_order(BordersOrder::Ordered(2)).unwrap();
+    let fill_values = FillValues::new(vec![FillType::RandomBetween(1, 3), FillType::ValueFrom(vec![0.5])], 1, false)
+        .unwrap();
+
+    let params = Params3D::new(axis_x, axis_y, Some(axis_z), Arc::new(borders), Arc::new(vec![fill_values]))
+        .with_faults(Arc::new(vec![Fault::new(30.0, 60.0, 2.0, 1.0, -2).unwrap()]));
+
+    let json = params.serialize_json();
+    let loaded = Params3D::deserialize_json(&json).unwrap();
+    assert_eq!(json, loaded.serialize_json());
+}
+
+// Axes with values between input units (odd steps in millis, refined axes) must be loaded exactly
+#[test]
+fn axis_exact_round_trip_tests() {
+    let axes = [
+        Axis::generate_axis_on_centers(1.0, 2.0, Some(0.125)).unwrap(),
+        Axis::generate_axis_on_centers(0.0, 0.01, Some(0.003)).unwrap(),
+        Axis::from_vec_as_centers(&[0.0, 0.003, 0.01]).unwrap(),
+        Axis::generate_axis_on_edges(0.0, 0.009, Some(0.003)).unwrap().coarsen(2).unwrap(),
+    ];
+
+    for axis in axes {
+        let loaded = Axis::deserialize_json(&axis.serialize_json()).unwrap();
+        assert_eq!(loaded.edges_fixed(), axis.edges_fixed());
+        assert_eq!(loaded.centers_fixed(), axis.centers_fixed());
+        assert_eq!(loaded.step_fixed(), axis.step_fixed());
+    }
+    assert_eq!(Axis::generate_axis_on_centers(1.0, 2.0, Some(0.125)).unwrap().start_fixed(), 9375);
+
+    // Hand-written edges without centers are still rounded to input precision
+    let loaded = Axis::deserialize_json(r#"{"blocks_edges": [0, 1.0004]}"#).unwrap();
+    assert_eq!(*loaded.blocks_edges(), vec![0.0, 1.0]);
+}
+
+// Config files may use short descriptions of axes, and every part must be validated
+#[test]
+fn params_from_file_tests() {
+    let config = r#"{
+        "axis_x": {"start": 0, "end": 10, "step": 0.5},
+        "axis_y": {"blocks_centers": [1, 2, 4]},
+        "borders_params": {"number_of_borders": 2, "borders_type": [{"RandomWithStep": [2, 1.0]}], "borders_limits": [[1, 5]]},
+        "fill_values": [{"fill_values": [{"RandomBetween": [1, 5]}]}]
+    }"#;
+    let config_path = std::env::temp_dir().join("grunt_params_from_file_test.json");
+    std::fs::write(&config_path, config).unwrap();
+
+    let params = Params3D::from_file(&config_path).unwrap();
+    std::fs::remove_file(&config_path).unwrap();
+
+    assert_eq!(params.axis_x().blocks_count(), 20);
+    assert_eq!(*params.axis_y().blocks_edges(), vec![0.5, 1.5, 3.0, 5.0]);
+    assert!(params.axis_z().is_none());
+    assert_eq!(params.borders().number_of_borders(), 2);
+    assert!(params.fill_values()[0].is_preset_ordered());
+    assert!(params.faults().is_empty());
+
+    let bad_config = config.replace("[[1, 5]]", "[[5, 1]]");
+    assert!(Params3D::deserialize_json(&bad_config).is_err());
+    let bad_config = config.replace("\"step\": 0.5", "\"step\": 0.0001");
+    assert!(Params3D::deserialize_json(&bad_config).is_err());
+    let bad_config = config.replace("[1, 2, 4]", "[1, 4, 2]");
+    let error = Params3D::deserialize_json(&bad_config).unwrap_err();
+    assert!(error.msg.contains("value 2 at 2 follows 4"), "{}", error.msg);
+    assert!(matches!(Params3D::from_file("grunt_missing_config.json"), Err(GruntError::Io(_))));
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, Params3D};
+use super::model3d::{dataset_seed, generate_dataset, generate_dataset_with, generate_model3d_seeded, DatasetError};
+
+fn dataset_test_params() -> Arc<Params3D> {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 12, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(2, &[BorderType::RandomWithStep(2, 0.5)], &[[5, 20], [30, 40]]).unwrap());
+    Params3D::new(axis.clone(), axis, None, borders, Arc::new(vec![FillValues::default()]))
+}
+
+// Dataset must be reproducible, every model must have its own seed and share params
+#[test]
+fn generate_dataset_tests() {
+    let params = dataset_test_params();
+    let first = generate_dataset(params.clone(), 6, 9).unwrap();
+    let second = generate_dataset(params.clone(), 6, 9).unwrap();
+
+    assert_eq!(first.len(), 6);
+    for (model_num, (first_model, second_model)) in first.iter().zip(second.iter()).enumerate() {
+        assert_eq!(first_model.seed(), Some(dataset_seed(9, model_num)));
+        assert_eq!(first_model.borders(), second_model.borders());
+        assert!(Arc::ptr_eq(&first_model.params(), &params));
+    }
+    assert_ne!(first[0].borders(), first[1].borders());
+
+    let single = generate_model3d_seeded(params, dataset_seed(9, 4)).unwrap();
+    assert_eq!(single.borders(), first[4].borders());
+}
+
+// Every model must be passed to callback once, callback errors must stop generation
+#[test]
+fn generate_dataset_callback_tests() {
+    let calls = AtomicUsize::new(0);
+    let max_done = AtomicUsize::new(0);
+    generate_dataset_with(dataset_test_params(), 10, 1, |_model_num, _model, done| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        max_done.fetch_max(done, Ordering::Relaxed);
+        Ok(())
+    }).unwrap();
+    assert_eq!(calls.into_inner(), 10);
+    assert_eq!(max_done.into_inner(), 10);
+
+    let result = generate_dataset_with(dataset_test_params(), 10, 1, |model_num, _model, _done| {
+        match model_num {
+            3 => Err(std::io::Error::other("export failed")),
+            _ => Ok(()),
+        }
+    });
+    assert!(matches!(result, Err(DatasetError::Export { model_num: 3, .. })));
+}
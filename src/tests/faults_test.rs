@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, Fault, FillValues, Params3D};
+use super::model3d::{generate_model3d, validate_layer, BorderValidationError};
+
+fn fault_test_params(axis_end: u32, faults: Vec<Fault>) -> Arc<Params3D> {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, axis_end, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(2, &[BorderType::RandomWithStep(1, 0.0)], &[[10, 10]]).unwrap());
+
+    Params3D::new(axis.clone(), axis, None, borders, Arc::new(vec![FillValues::default()]))
+        .with_faults(Arc::new(faults))
+}
+
+// Vertical fault must move every border on one side of its trace
+#[test]
+fn vertical_fault_tests() {
+    let fault = Fault::new(0.0, 90.0, 5.0, 0.0, 4).unwrap();
+    let model = generate_model3d(fault_test_params(10, vec![fault.clone()])).unwrap();
+
+    assert_eq!(*model.faults(), vec![fault]);
+    for border_num in 0..model.borders().borders_count() {
+        for y_row in model.borders().rows(border_num) {
+            assert_eq!(y_row, [10, 10, 10, 10, 10, 14, 14, 14, 14, 14]);
+        }
+    }
+}
+
+// Dipping fault must move only borders above its plane
+#[test]
+fn dipping_fault_tests() {
+    let faults = vec![Fault::new(0.0, 45.0, 0.0, 0.0, -3).unwrap(), Fault::new(90.0, 90.0, 0.0, 15.0, 2).unwrap()];
+    let model = generate_model3d(fault_test_params(20, faults)).unwrap();
+
+    for (y_id, y_row) in model.borders().rows(0).enumerate() {
+        for (x_id, value) in y_row.iter().enumerate() {
+            let expected = 10 - if x_id >= 10 { 3 } else { 0 } + if y_id < 15 { 2 } else { 0 };
+            assert_eq!(*value, expected);
+        }
+    }
+}
+
+// Step overflow is allowed only along fault trace
+#[test]
+fn fault_validation_tests() {
+    let border = [5, 9, 9, 5, 9, 9];
+    let offsets = [0, 4, 4, 0, 4, 4];
+    assert_eq!(validate_layer(0, &border, (2, 3), [5, 5], Some(1), Some(&offsets)), Ok(()));
+
+    let offsets = [0, 4, 4, 0, 0, 4];
+    assert_eq!(validate_layer(0, &border, (2, 3), [5, 9], Some(1), Some(&offsets)),
+        Err(BorderValidationError::StepOverflowLeft { layer_id: 0, y: 1, x: 1 }));
+
+    assert!(Fault::new(-1.0, 45.0, 0.0, 0.0, 1).is_err());
+    assert!(Fault::new(10.0, 0.0, 0.0, 0.0, 1).is_err());
+    assert!(Fault::new(10.0, 91.0, 0.0, 0.0, 1).is_err());
+    assert!(Fault::new(10.0, 60.0, f64::NAN, 0.0, 1).is_err());
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, FillType, Params3D};
+use super::model3d::generate_model3d;
+
+fn fill_test_params(fill_values: Vec<FillValues>) -> Arc<Params3D> {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 6, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(3, &[BorderType::Random], &[[5, 10]]).unwrap());
+
+    Params3D::new(axis.clone(), axis, None, borders, Arc::new(fill_values))
+}
+
+// Every layer between borders must be filled and every value must be inside fill type limits
+#[test]
+fn fill_values_generation_tests() {
+    let fill_values = FillValues::new(vec![FillType::RandomBetween(-3, 7)], 0, true).unwrap();
+    let model = generate_model3d(fill_test_params(vec![fill_values])).unwrap();
+
+    assert_eq!(model.fill_values().len(), 4);
+    for layer in model.fill_values() {
+        assert_eq!(layer.len(), 6);
+        assert!(layer.iter().all(|row| row.len() == 6));
+        assert!(layer.iter().flatten().all(|value| (-3.0..=7.0).contains(value)));
+    }
+
+    assert!(model.fill_value(3, 5, 5).is_some());
+    assert!(model.fill_value(4, 0, 0).is_none());
+    assert!(model.fill_value(0, 6, 0).is_none());
+}
+
+// Ordered presets must be used one by one, smoothing must save constant layers
+#[test]
+fn fill_values_ordered_tests() {
+    let ordered = FillValues::new(vec![FillType::ValueFrom(vec![1.5]), FillType::ValueFrom(vec![4.0])], 2, true)
+        .unwrap();
+    let constant = FillValues::new(vec![FillType::RandomBetween(9, 9)], 0, false).unwrap();
+    let model = generate_model3d(fill_test_params(vec![ordered, constant])).unwrap();
+
+    let expected = [1.5, 9.0, 4.0, 9.0];
+    for (layer, expected_value) in model.fill_values().iter().zip(expected) {
+        assert!(layer.iter().flatten().all(|value| *value == expected_value));
+    }
+
+    assert!(FillValues::new(vec![], 0, true).is_err());
+    assert!(FillValues::new(vec![FillType::RandomBetween(2, 1)], 0, true).is_err());
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, Params3D};
+use super::model3d::{generate_model3d_seeded, Model3D};
+
+fn gis_test_model(axis_y: Axis, axis_z: Option<Axis>) -> Model3D {
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(10, 13, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(2, &[BorderType::Random], &[[1, 3], [4, 5]]).unwrap());
+    let params = Params3D::new(axis_x, Arc::new(axis_y), axis_z.map(Arc::new), borders,
+        Arc::new(vec![FillValues::default()]));
+
+    generate_model3d_seeded(params, 5).unwrap()
+}
+
+// Every border must be written as separate grid with first y row at the bottom
+#[test]
+fn export_asc_tests() {
+    let model = gis_test_model(Axis::from_vec_as_edges(&[0, 1, 2]).unwrap(), None);
+    let path = std::env::temp_dir().join("grunt_asc_test");
+    model.export_asc(path.to_str().unwrap()).unwrap();
+
+    for border_num in 0..model.borders().borders_count() {
+        let path = std::env::temp_dir().join(format!("grunt_asc_test_bo{border_num}.asc"));
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[..5], ["ncols 3", "nrows 2", "xllcorner 10", "yllcorner 0", "cellsize 1"]);
+
+        let last_row: Vec<String> = model.borders().row(border_num, 0).iter().map(|value| value.to_string()).collect();
+        assert_eq!(lines[6], last_row.join(" "));
+    }
+
+    let model = gis_test_model(Axis::from_vec_as_edges(&[0, 1, 3]).unwrap(), None);
+    assert!(model.export_asc(path.to_str().unwrap()).is_err());
+
+    // Non-square cells need dx and dy keys, which are supported only by GDAL
+    let model = gis_test_model(Axis::generate_axis_on_edges(0, 4, Some(2)).unwrap(), None);
+    assert!(model.export_asc(path.to_str().unwrap()).is_err());
+}
+
+// Every block must be written as its centers and depth from axis_z
+#[test]
+fn export_xyz_tests() {
+    let axis_z = Axis::from_vec_as_edges(&[0, 1, 2, 4, 8, 16, 32]).unwrap();
+    let model = gis_test_model(Axis::from_vec_as_edges(&[0, 1, 3]).unwrap(), Some(axis_z.clone()));
+    let path = std::env::temp_dir().join("grunt_xyz_test");
+    model.export_xyz(path.to_str().unwrap()).unwrap();
+
+    for border_num in 0..model.borders().borders_count() {
+        let path = std::env::temp_dir().join(format!("grunt_xyz_test_bo{border_num}.xyz"));
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[5], format!("12.5 2 {}", axis_z.blocks_edges()[model.borders()[(border_num, 1, 2)] as usize]));
+    }
+}
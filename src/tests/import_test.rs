@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use nanoserde::SerJson;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, Params3D};
+use super::model3d::{generate_model3d, Model3D, ModelImportError};
+use super::model3d::export::ExportOptions;
+use super::error::GruntError;
+
+fn import_test_model() -> Model3D {
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(0, 7, Some(1)).unwrap());
+    let axis_y = Arc::new(Axis::from_vec_as_centers(&[1, 2, 4, 8]).unwrap());
+    let borders = Arc::new(BordersParams::new(3, &[BorderType::RandomWithStep(2, 0.5)], &[[5, 20], [30, 40]]).unwrap());
+    let params = Params3D::new(axis_x, axis_y, None, borders, Arc::new(vec![FillValues::default()]));
+
+    generate_model3d(params).unwrap()
+}
+
+fn export_and_import(model: &Model3D, name: &str, save: &ExportOptions) -> Result<Model3D, GruntError> {
+    let path = std::env::temp_dir().join(name);
+    model.export_model(path.to_str().unwrap(), save).unwrap();
+
+    let path = path.with_extension("json");
+    let imported = Model3D::import_model(&path);
+    std::fs::remove_file(&path).unwrap();
+    imported
+}
+
+// Exported model must be imported with the same params and borders
+#[test]
+fn import_model_tests() {
+    let model = import_test_model();
+
+    let imported = export_and_import(&model, "grunt_import_full_test", &ExportOptions::new().with_params().with_borders()).unwrap();
+    assert_eq!(imported.params().serialize_json(), model.params().serialize_json());
+    assert_eq!(imported.borders(), model.borders());
+    assert_eq!(imported.seed(), None);
+
+    let options = ExportOptions::new().with_params().with_borders().with_metadata();
+    let imported = export_and_import(&model, "grunt_import_metadata_test", &options).unwrap();
+    assert_eq!(imported.seed(), model.seed());
+    assert!(imported.seed().is_some());
+
+    let imported = export_and_import(&model, "grunt_import_params_test", &ExportOptions::new().with_params()).unwrap();
+    assert_eq!(imported.params().serialize_json(), model.params().serialize_json());
+    assert!(imported.borders().is_empty());
+
+    let imported = export_and_import(&model, "grunt_import_borders_test", &ExportOptions::new().with_borders()).unwrap();
+    assert_eq!(imported.borders(), model.borders());
+    assert_eq!(imported.params().axis_x().blocks_count(), 7);
+    assert_eq!(imported.params().axis_y().blocks_count(), 4);
+    assert_eq!(imported.params().borders().number_of_borders(), 3);
+
+    let imported = export_and_import(&model, "grunt_import_empty_test", &ExportOptions::new());
+    assert!(imported.is_err());
+}
+
+// Borders which don't match axes must not be imported
+#[test]
+fn import_model_sizes_tests() {
+    let path = std::env::temp_dir().join("grunt_import_sizes_test.json");
+    let params = import_test_model().params().serialize_json();
+
+    let mut model_json = format!("{{\"params3D\":{params},\"borders\":[{{\"bo0\":[{{\"y0\":[1,2]}}]}}]}}");
+    std::fs::write(&path, &model_json).unwrap();
+    let error = Model3D::import_model(&path).unwrap_err();
+    assert!(matches!(error, GruntError::Import(ModelImportError::BordersCount { expected: 3, found: 1 })));
+
+    model_json = model_json.replace("bo0", "bo1");
+    std::fs::write(&path, &model_json).unwrap();
+    assert!(Model3D::import_model(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+// Model written to any writer must be the same as model written to file
+#[test]
+fn export_to_writer_tests() {
+    let model = import_test_model();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let options = ExportOptions::new().with_params().with_borders();
+    model.export_to(&mut buffer, &options).unwrap();
+
+    let path = std::env::temp_dir().join("grunt_export_to_test");
+    model.export_model(path.to_str().unwrap(), &options).unwrap();
+    let path = path.with_extension("json");
+    assert_eq!(std::fs::read(&path).unwrap(), buffer);
+    std::fs::remove_file(&path).unwrap();
+
+    buffer.clear();
+    model.export_to(&mut buffer, &ExportOptions::new()).unwrap();
+    assert_eq!(buffer, b"{\"params3D\":null,\"borders\":null}");
+}
+
+// Every selected section must be exported, fill values must be imported with any index base
+#[test]
+fn export_options_tests() {
+    let model = import_test_model();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    model.export_to(&mut buffer, &ExportOptions::all().with_index_base(1).with_float_precision(2)).unwrap();
+    let model_json = String::from_utf8(buffer).unwrap();
+
+    assert!(model_json.contains("\"borders\":[{\"bo1\":[{\"y1\":["));
+    assert!(model_json.contains("\"fill_values\":[{\"la1\":[{\"y1\":["));
+    assert!(model_json.contains("\"voxels\":null"));
+    assert!(model_json.contains("\"statistics\":{\"borders\":[{\"min\":"));
+    assert!(model_json.contains("\"index_base\":1,\"float_precision\":2}"));
+
+    let imported = export_and_import(&model, "grunt_import_options_test", &ExportOptions::all()).unwrap();
+    assert_eq!(imported.borders(), model.borders());
+    assert_eq!(imported.fill_values(), model.fill_values());
+
+    let path = std::env::temp_dir().join("grunt_import_base_test.json");
+    std::fs::write(&path, &model_json).unwrap();
+    let imported = Model3D::import_model(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(imported.borders(), model.borders());
+    assert_eq!(imported.fill_values().len(), model.fill_values().len());
+}
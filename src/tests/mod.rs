@@ -1,3 +1,17 @@
 use super::*;
 
 mod axis_test;
+mod fill_values_test;
+mod voxels_test;
+mod seed_test;
+mod borders_test;
+mod faults_test;
+mod config_test;
+mod import_test;
+mod binary_test;
+mod gis_test;
+mod vtk_test;
+mod cli_test;
+mod dataset_test;
+mod border_grid_test;
+mod resample_test;
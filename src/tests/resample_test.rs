@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use super::types::{Axis, AxisError, BordersParams, BorderType, FillValues, Params3D};
+use super::model3d::{generate_model3d_seeded, Interpolation};
+
+// Refined, coarsened and sliced axes must keep edges of original axis
+#[test]
+fn axis_resampling_tests() {
+    let ax = Axis::from_vec_as_edges(&[0.0, 1.0, 1.5, 4.0]).unwrap();
+
+    let refined = ax.refine(3).unwrap();
+    assert_eq!(*refined.blocks_edges(), vec![0.0, 0.333, 0.667, 1.0, 1.167, 1.333, 1.5, 2.333, 3.167, 4.0]);
+    assert_eq!(refined.coarsen(3).unwrap().blocks_edges(), ax.blocks_edges());
+    assert_eq!(ax.refine(1).unwrap().blocks_edges(), ax.blocks_edges());
+    assert!(matches!(ax.refine(0), Err(AxisError::InvalidFactor)));
+    let minimal_step = AxisError::MinimalStep { index: 2, prev: 0.002, value: 0.003 };
+    assert_eq!(Axis::from_vec_as_edges(&[0.0, 0.003]).unwrap().refine(2).err(), Some(minimal_step));
+
+    // Edges between input units must be kept by refined axis
+    let ax = Axis::from_vec_as_centers(&[1.0, 2.0, 3.001]).unwrap();
+    assert_eq!(ax.edges_fixed(), &[5000, 15000, 25005, 35015]);
+    let refined = ax.refine(2).unwrap();
+    assert_eq!(refined.edges_fixed(), &[5000, 10000, 15000, 20000, 25005, 30015, 35015]);
+    for block_id in 0..refined.blocks_count() {
+        assert_eq!(ax.locate(refined.center(block_id)), Some(block_id / 2));
+    }
+
+    let ax = Axis::generate_axis_on_edges(0.0, 0.9, Some(0.3)).unwrap();
+    let refined = ax.refine(2).unwrap();
+    assert_eq!(refined.step(), Some(0.15));
+    assert_eq!(*refined.blocks_centers(), vec![0.075, 0.225, 0.375, 0.525, 0.675, 0.825]);
+    assert_eq!(ax.refine(7).unwrap().step(), None);
+    assert_eq!(ax.coarsen(3).unwrap().step(), Some(0.9));
+    assert_eq!(*ax.coarsen(5).unwrap().blocks_edges(), vec![0.0, 0.9]);
+
+    let sliced = ax.slice(1..3).unwrap();
+    assert_eq!((sliced.start(), sliced.end(), sliced.step()), (0.3, 0.9, Some(0.3)));
+    assert_eq!(ax.slice(2..4).err(), Some(AxisError::InvalidBlocksRange { start: 2, end: 4, blocks_count: 3 }));
+    assert!(matches!(ax.slice(1..1), Err(AxisError::InvalidBlocksRange { .. })));
+
+    let coarse = ax.coarsen(3).unwrap();
+    assert_eq!(coarse.resample(&ax), vec![[(0, 1.0), (0, 0.0)]; 3]);
+}
+
+// Model on the same axes mustn't change, refined model must keep values of original blocks
+#[test]
+fn model_resample_tests() {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 8, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(2, &[BorderType::Random], &[[5, 20], [30, 40]]).unwrap());
+    let params = Params3D::new(axis.clone(), axis.clone(), None, borders, Arc::new(vec![FillValues::default(); 3]));
+    let model = generate_model3d_seeded(params, 4).unwrap();
+
+    let same = model.resample(axis.clone(), axis.clone(), Interpolation::Linear);
+    assert_eq!(same.borders(), model.borders());
+    assert_eq!(same.fill_values(), model.fill_values());
+    assert_eq!(same.seed(), None);
+
+    let fine_axis = Arc::new(axis.refine(2).unwrap());
+    let nearest = model.resample(fine_axis.clone(), axis.clone(), Interpolation::Nearest);
+    assert_eq!(nearest.borders().shape(), (2, 8, 16));
+    assert_eq!(nearest.params().axis_x().blocks_count(), 16);
+    assert_eq!(nearest.borders().get(1, 3, 5), model.borders().get(1, 3, 2));
+    assert_eq!(nearest.fill_value(2, 7, 15), model.fill_value(2, 7, 7));
+    assert!(nearest.validate().is_ok());
+
+    let linear = model.resample(fine_axis, axis, Interpolation::Linear);
+    let (left, right) = (model.borders()[(0, 4, 2)] as f64, model.borders()[(0, 4, 3)] as f64);
+    assert_eq!(linear.borders()[(0, 4, 6)], (left * 0.25 + right * 0.75).round() as u32);
+    assert!(linear.validate().is_ok());
+}
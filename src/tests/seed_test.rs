@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, FillType, Params3D};
+use super::model3d::{generate_model3d_seeded, BorderValidationError};
+
+fn seed_test_params(number_of_borders: u8) -> Arc<Params3D> {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 20, Some(1)).unwrap());
+    let borders_type = [BorderType::Random, BorderType::RandomWithStep(2, 1.0)];
+    let borders = Arc::new(BordersParams::new(number_of_borders, &borders_type, &[[10, 40], [50, 90]]).unwrap());
+    let fill_values = FillValues::new(vec![FillType::RandomBetween(0, 100), FillType::ValueFrom(vec![1.0, 2.0])], 1, false)
+        .unwrap();
+
+    Params3D::new(axis.clone(), axis, None, borders, Arc::new(vec![fill_values]))
+}
+
+// Same seed must give same model, different seeds - different models
+#[test]
+fn seeded_generation_tests() {
+    let first = generate_model3d_seeded(seed_test_params(4), 42).unwrap();
+    let second = generate_model3d_seeded(seed_test_params(4), 42).unwrap();
+    let third = generate_model3d_seeded(seed_test_params(4), 43).unwrap();
+
+    assert_eq!(first.seed(), Some(42));
+    assert_eq!(first.borders(), second.borders());
+    assert_eq!(first.fill_values(), second.fill_values());
+    assert_ne!(first.borders(), third.borders());
+}
+
+// Adding borders must not change borders and layers before them
+#[test]
+fn seeded_generation_stability_tests() {
+    let small = generate_model3d_seeded(seed_test_params(2), 7).unwrap();
+    let big = generate_model3d_seeded(seed_test_params(5), 7).unwrap();
+
+    assert_eq!(small.borders().as_slice(), &big.borders().as_slice()[..small.borders().as_slice().len()]);
+    assert_eq!(small.fill_values()[..], big.fill_values()[..3]);
+}
+
+// Borders generated in parallel must not depend on threads, error must belong to the first invalid border
+#[test]
+fn parallel_borders_generation_tests() {
+    let first = generate_model3d_seeded(seed_test_params(64), 21).unwrap();
+    let second = generate_model3d_seeded(seed_test_params(64), 21).unwrap();
+    assert_eq!(first.borders(), second.borders());
+    let small = generate_model3d_seeded(seed_test_params(5), 21).unwrap();
+    assert_eq!(first.borders().as_slice()[..small.borders().as_slice().len()], *small.borders().as_slice());
+
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(0, 20, Some(1)).unwrap());
+    let axis_y = Arc::new(Axis::generate_axis_on_edges(0, 1, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(32, &[BorderType::Random], &[[10, 40]]).unwrap());
+    let params = Params3D::new(axis_x, axis_y, None, borders, Arc::new(vec![FillValues::default()]));
+    for _ in 0..5 {
+        let error = generate_model3d_seeded(params.clone(), 1).unwrap_err();
+        assert_eq!(error, BorderValidationError::GridTooSmall { layer_id: 0 });
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, FillType, Params3D};
+use super::model3d::generate_model3d;
+
+// Voxels must be split by borders: voxel z belongs to layer n when n borders are <= z
+#[test]
+fn voxels_generation_tests() {
+    let axis = Arc::new(Axis::generate_axis_on_edges(0, 4, Some(1)).unwrap());
+    let axis_z = Arc::new(Axis::generate_axis_on_edges(0, 10, Some(1)).unwrap());
+    let borders = Arc::new(BordersParams::new(2, &[BorderType::Random], &[[3, 3], [6, 6]]).unwrap());
+    let fill_values = FillValues::new(
+        vec![FillType::ValueFrom(vec![1.0]), FillType::ValueFrom(vec![2.0]), FillType::ValueFrom(vec![3.0])],
+        0,
+        true,
+    ).unwrap();
+
+    let params = Params3D::new(axis.clone(), axis.clone(), Some(axis_z), borders.clone(), Arc::new(vec![fill_values]));
+    let model = generate_model3d(params).unwrap();
+
+    let voxels = model.to_voxels().unwrap();
+    assert_eq!((voxels.len(), voxels[0].len(), voxels[0][0].len()), (10, 4, 4));
+    let column: Vec<u8> = voxels.iter().map(|z_slice| z_slice[2][1]).collect();
+    assert_eq!(column, vec![0, 0, 0, 1, 1, 1, 2, 2, 2, 2]);
+
+    let filled = model.to_voxels_filled().unwrap();
+    let column: Vec<f32> = filled.iter().map(|z_slice| z_slice[0][3]).collect();
+    assert_eq!(column, vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 3.0, 3.0, 3.0]);
+
+    let params = Params3D::new(axis.clone(), axis, None, borders, Arc::new(vec![FillValues::default()]));
+    assert!(generate_model3d(params).unwrap().to_voxels().is_none());
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use super::types::{Axis, BordersParams, BorderType, FillValues, FillType, Params3D};
+use super::model3d::generate_model3d_seeded;
+
+// VTK grid must be built on axes edges, borders must lie on zero contour of their point data
+#[test]
+fn export_vtk_tests() {
+    let axis_x = Arc::new(Axis::generate_axis_on_edges(0, 2, Some(1)).unwrap());
+    let axis_y = Arc::new(Axis::from_vec_as_edges(&[0, 1, 3, 7]).unwrap());
+    let axis_z = Arc::new(Axis::from_vec_as_edges(&[0.0, 0.5, 1.5, 3.0]).unwrap());
+    let borders = Arc::new(BordersParams::new(1, &[BorderType::Random], &[[2, 2]]).unwrap());
+    let fill_values = FillValues::new(vec![FillType::ValueFrom(vec![1.5]), FillType::ValueFrom(vec![4.0])], 0, true)
+        .unwrap();
+    let params = Params3D::new(axis_x, axis_y, Some(axis_z), borders, Arc::new(vec![fill_values]));
+    let model = generate_model3d_seeded(params, 3).unwrap();
+
+    let path = std::env::temp_dir().join("grunt_vtk_test");
+    model.export_vtk(path.to_str().unwrap()).unwrap();
+    let path = path.with_extension("vtk");
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let lines: Vec<&str> = content.lines().collect();
+    let line_after = |header: &str| lines[lines.iter().position(|line| *line == header).unwrap() + 1];
+
+    assert_eq!(lines[4], "DIMENSIONS 3 4 4");
+    assert_eq!(line_after("Y_COORDINATES 4 double"), "0 1 3 7");
+    assert_eq!(line_after("Z_COORDINATES 4 double"), "0 0.5 1.5 3");
+    assert!(content.contains("CELL_DATA 18\nSCALARS layer_id int 1\nLOOKUP_TABLE default\n0 0 0 0 0 0 0 0 0\n0 0 0 1 1 1 1 1 1\n"));
+    assert!(content.contains("SCALARS fill_value float 1\nLOOKUP_TABLE default\n1.5 1.5 1.5"));
+
+    // 12 points on every Z level, border lies on edge 2 (Z = 1.5)
+    assert_eq!(line_after("POINT_DATA 48"), "SCALARS border_0 double 1");
+    let border_data: Vec<f64> = lines[lines.len() - 6..].join(" ").split(' ').map(|value| value.parse().unwrap()).collect();
+    assert_eq!(border_data.len(), 48);
+    assert!(border_data[24..36].iter().all(|value| *value == 0.0));
+    assert!(border_data[..24].iter().all(|value| *value < 0.0));
+}
@@ -1,46 +1,101 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use nanoserde::SerJson;
+use nanoserde::{DeJson, SerJson};
 
 mod axis;
-pub use axis::AxisError;
+pub use axis::{AxisError, AXIS_DECIMALS, FIXED_SCALE};
 
 mod borders;
+pub use borders::BorderError;
+
 mod fill_values;
+pub use fill_values::FillValuesError;
+
+mod fault;
+pub use fault::FaultError;
 
 mod params3d;
 
+// Raw json representations of types, they are validated by constructors after deserialization.
+// Derived deserialization of optional fields doesn't pass clippy
+#[allow(clippy::question_mark)]
+mod json;
+
 /// Struct to store Axis and some related params.
 ///
-/// 1. Axis should ensure any elements from -10000 to 10000 with 3 decimal places.
+/// 1. Input values are rounded to AXIS_DECIMALS (3) decimal places.
 /// 2. Axis can only be sequential.
 /// 3. Start and End for axis are edges, not centers.
 ///
+/// Edges and centers are stored as integers in 1 / FIXED_SCALE units, so input values and centers
+/// between them are exact, f64 values are computed from them on first access.
+///
 /// Axis can be devided to two parts: edges and centers. You should think about Axis as a struct to
 /// store grid(image) data, so every block inside it have 2 edges and 1 center.
-#[derive(Debug, Clone, SerJson)]
+#[derive(Debug, Clone)]
 pub struct Axis {
-    /// First edge for axis
-    start: f64,
-    /// Last edge for axis
-    end: f64,
-    /// Step between edges if exists
-    step: Option<f64>,
-    /// Number of centers(blocks)
-    blocks_count: usize,
-    /// Center positions
-    blocks_centers: Vec<f64>,
-    /// Edge positions
-    blocks_edges: Vec<f64>,
+    /// Step between edges in fixed-point units if exists
+    step: Option<i64>,
+    /// Center positions in fixed-point units
+    centers: Vec<i64>,
+    /// Edge positions in fixed-point units
+    edges: Vec<i64>,
+    /// Center positions, computed on first access
+    blocks_centers: OnceLock<Vec<f64>>,
+    /// Edge positions, computed on first access
+    blocks_edges: OnceLock<Vec<f64>>,
 }
 
 /// Enum determines method to generate borders
-#[derive(Debug, Clone, SerJson)]
+#[derive(Debug, Clone, SerJson, DeJson)]
 pub enum BorderType {
     /// Random value between limits
     Random,
     /// Random value between limits with step < max step(1) and probability of step(2)
     RandomWithStep(u16, f32),
+    /// Fractal gradient noise based on physical coordinates of blocks, every next octave has
+    /// 2 times smaller wavelength and persistence times smaller amplitude
+    Noise {
+        /// Number of summed noises
+        octaves: u8,
+        /// Amplitude multiplier for every next octave
+        persistence: f32,
+        /// Wavelength of first octave in axis units
+        wavelength: f32,
+    },
+    /// Gaussian random field with mean in the middle of limits. Generation fails if field for
+    /// axes needs too big circulant embedding (very small blocks or long range)
+    GaussianField {
+        /// Covariance model of structured part of field
+        model: CovarianceModel,
+        /// Practical range of covariance in axis units
+        range: f32,
+        /// Variance of structured part of field
+        sill: f32,
+        /// Variance of uncorrelated part of field
+        nugget: f32,
+    },
+}
+
+/// Enum determines covariance model of gaussian random field, range is practical range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SerJson, DeJson)]
+pub enum CovarianceModel {
+    /// Covariance decreases as 1 - 1.5h + 0.5h^3 and reaches 0 at range
+    Spherical,
+    /// Covariance decreases as exp(-3h)
+    Exponential,
+    /// Covariance decreases as exp(-3h^2)
+    Gaussian,
+}
+
+/// Enum determines how every border is placed relative to previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SerJson, DeJson)]
+pub enum BordersOrder {
+    /// Every border is generated independently, so borders can cross each other
+    Free,
+    /// Every border is at least min thickness(1) below previous one, 0 means that borders can only
+    /// touch each other
+    Ordered(u32),
 }
 
 /// Stucts to determine borders params. That means that every layer will look for it borders params
@@ -54,10 +109,12 @@ pub struct BordersParams {
     borders_type: Vec<BorderType>,
     /// Determines limits within every layer will be generated
     borders_limits: Vec<[u32; 2]>,
+    /// Determines placement of borders relative to each other
+    borders_order: BordersOrder,
 }
 
 /// Enum determines method to fill values for every layer
-#[derive(Debug, Clone, SerJson)]
+#[derive(Debug, Clone, SerJson, DeJson)]
 pub enum FillType {
     /// Random value between two, both incl
     RandomBetween(i32, i32),
@@ -76,10 +133,29 @@ pub struct FillValues {
     is_preset_ordered: bool,
 }
 
+/// Struct to store fault plane and displacement of its hanging wall
+#[derive(Debug, Clone, PartialEq, SerJson)]
+pub struct Fault {
+    /// Azimuth of fault trace in degrees, measured clockwise from Y axis
+    strike: f64,
+    /// Angle between fault plane and horizontal plane in degrees, plane dips to the right of strike
+    dip: f64,
+    /// X coordinate of point where fault plane crosses top of model
+    x: f64,
+    /// Y coordinate of point where fault plane crosses top of model
+    y: f64,
+    /// Vertical displacement of hanging wall in borders units, positive moves it down
+    throw: i32,
+}
+
 #[derive(Debug, Clone, SerJson)]
 pub struct Params3D {
     axis_x: Arc<Axis>,
     axis_y: Arc<Axis>,
+    /// Depth axis, border with value n lies on its n edge
+    axis_z: Option<Arc<Axis>>,
     borders_params: Arc<BordersParams>,
     fill_values: Arc<Vec<FillValues>>,
+    /// Faults applied to borders after generation one by one
+    faults: Arc<Vec<Fault>>,
 }
@@ -1,31 +1,144 @@
+use std::str::Chars;
+use std::sync::OnceLock;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState, SerJson, SerJsonState};
+
+use crate::types::json::AxisJson;
 use crate::types::Axis;
 
-// Possible errors during Axis creation
-#[derive(Debug, Clone)]
+// Possible errors during Axis creation, values are rounded as in axis
+#[derive(Debug, Clone, PartialEq)]
 pub enum AxisError {
-    InvalidRange,
-    TooSmallStep,
-    NotOrderedVec,
-    TooSmallVec,
-    MinimalStep,
+    InvalidRange { start: f64, end: f64 },
+    TooSmallStep { step: f64 },
+    NotOrderedVec { index: usize, prev: f64, value: f64 },
+    TooSmallVec { len: usize },
+    MinimalStep { index: usize, prev: f64, value: f64 },
     NotEnoughElements,
+    InvalidFactor,
+    InvalidBlocksRange { start: usize, end: usize, blocks_count: usize },
+    InvalidGrowth { growth: f64 },
+    NotPositiveStart { start: f64 },
+    OutOfRange { value: f64 },
 }
 
 impl std::fmt::Display for AxisError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            AxisError::InvalidRange => writeln!(f, "End's value must be bigger than start's ones"),
-            AxisError::TooSmallStep => writeln!(f, "Step value is can't be smaller than 0.001"),
-            AxisError::NotOrderedVec => writeln!(f, "Values in input Vec must constanly increase"),
-            AxisError::TooSmallVec => writeln!(f, "Input vector must contain at least 2 elements"),
-            AxisError::MinimalStep => writeln!(f, "Minimal step for input vec must be at least 0.002"),
-            AxisError::NotEnoughElements => writeln!(f, "Every generated axis must have at least two elements"),
+            AxisError::InvalidRange { start, end } =>
+                write!(f, "End's value must be bigger than start's ones, start - {start}, end - {end}"),
+            AxisError::TooSmallStep { step } => write!(f, "Step value is can't be smaller than 0.001, found {step}"),
+            AxisError::NotOrderedVec { index, prev, value } =>
+                write!(f, "Values in input Vec must constanly increase, value {value} at {index} follows {prev}"),
+            AxisError::TooSmallVec { len } => write!(f, "Input vector must contain at least 2 elements, found {len}"),
+            AxisError::MinimalStep { index, prev, value } =>
+                write!(f, "Minimal step for input vec must be at least 0.002, value {value} at {index} follows {prev}"),
+            AxisError::NotEnoughElements => write!(f, "Every generated axis must have at least two elements"),
+            AxisError::InvalidFactor => write!(f, "Factor must be at least 1"),
+            AxisError::InvalidBlocksRange { start, end, blocks_count } =>
+                write!(f, "Blocks range {start}..{end} must be non-empty and inside axis with {blocks_count} blocks"),
+            AxisError::InvalidGrowth { growth } => write!(f, "Growth of steps must be finite and at least 1.0, found {growth}"),
+            AxisError::NotPositiveStart { start } =>
+                write!(f, "Start of logarithmic axis must be bigger than 0, found {start}"),
+            AxisError::OutOfRange { value } =>
+                write!(f, "Axis values must be finite and not bigger than {MAX_VALUE:e} by modulus, found {value}"),
         }
     }
 }
 
 impl std::error::Error for AxisError {}
 
+/// Number of decimal places kept for input values of axis
+pub const AXIS_DECIMALS: u32 = 3;
+
+/// Number of fixed-point units in 1.0, it has one more decimal place than input values, so
+/// center between any two input values is exact. Only axes built from such centers (refined or
+/// coarsened ones) can have values between fixed-point units, they are rounded half away from zero
+pub const FIXED_SCALE: i64 = 10_i64.pow(AXIS_DECIMALS + 1);
+
+// Fixed-point units in minimal input value (0.001)
+const INPUT_UNIT: i64 = FIXED_SCALE / 10_i64.pow(AXIS_DECIMALS);
+
+// Maximal modulus of axis value in fixed-point units, distance between any two values fits into i64
+const MAX_UNITS: i64 = i64::MAX / 4;
+
+// Maximal modulus of axis value
+const MAX_VALUE: f64 = (MAX_UNITS / FIXED_SCALE) as f64;
+
+/// Axis is deserialized using its constructors, so every loaded axis is valid:
+/// 0. If both edges and centers exist and they are exact values of fixed-point units (as axis is
+///    serialized), axis is rebuilt from edges without rounding, so serialized axis is loaded back
+///    without changes. Start, end, step and centers must match rebuilt axis, otherwise next rules
+///    are used
+/// 1. If step exists axis is generated on edges from start to end (or first and last edges)
+/// 2. Otherwise axis is created from edges or from centers, if both exist - from the one which
+///    gives the same edges and centers
+/// 3. Otherwise axis is generated on edges from start to end with default step
+///
+/// If blocks_count exists it must be equal to number of blocks in created axis
+impl DeJson for Axis {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Axis, DeJsonErr> {
+        let axis_json = AxisJson::de_json(state, input)?;
+
+        let edges = axis_json.blocks_edges.as_ref();
+        let centers = axis_json.blocks_centers.as_ref();
+        let start = axis_json.start.or_else(|| edges.and_then(|edges| edges.first().copied()));
+        let end = axis_json.end.or_else(|| edges.and_then(|edges| edges.last().copied()));
+
+        let stored_axis = edges.zip(centers)
+            .and_then(|(edges, centers)| Axis::from_stored_edges(edges, axis_json.step).filter(|axis| axis.blocks_centers() == centers))
+            .filter(|axis| axis_json.start.is_none_or(|start| axis.start() == start))
+            .filter(|axis| axis_json.end.is_none_or(|end| axis.end() == end));
+        if let Some(axis) = stored_axis {
+            if axis_json.blocks_count.is_some_and(|blocks_count| blocks_count != axis.blocks_count()) {
+                return Err(state.err_parse("Axis: blocks_count doesn't match created axis"))
+            }
+            return Ok(axis)
+        }
+
+        let axis = match (axis_json.step, edges, centers) {
+            (Some(step), _, _) if start.is_some() && end.is_some() => {
+                Axis::generate_axis_on_edges(start.unwrap(), end.unwrap(), Some(step))
+            },
+            (None, Some(edges), Some(centers)) => {
+                let axis = Axis::from_vec_as_edges(edges)
+                    .ok()
+                    .filter(|axis| axis.blocks_centers() == centers)
+                    .or_else(|| Axis::from_vec_as_centers(centers).ok().filter(|axis| axis.blocks_edges() == edges))
+                    .ok_or_else(|| state.err_parse("Axis: edges and centers don't match each other"))?;
+                Ok(axis)
+            },
+            (None, Some(edges), None) => Axis::from_vec_as_edges(edges),
+            (None, None, Some(centers)) => Axis::from_vec_as_centers(centers),
+            (step, _, _) if start.is_some() && end.is_some() => {
+                Axis::generate_axis_on_edges(start.unwrap(), end.unwrap(), step)
+            },
+            _ => return Err(state.err_parse("Axis: start and end, edges or centers are required")),
+        }.map_err(|err| state.err_parse(format!("Axis: {err}").as_str()))?;
+
+        if axis_json.blocks_count.is_some_and(|blocks_count| blocks_count != axis.blocks_count()) {
+            return Err(state.err_parse("Axis: blocks_count doesn't match created axis"))
+        }
+
+        Ok(axis)
+    }
+}
+
+// Axis is serialized with all its values, so it can be loaded back without generation params
+impl SerJson for Axis {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        let axis_json = AxisJson {
+            start: Some(self.start()),
+            end: Some(self.end()),
+            step: self.step(),
+            blocks_count: Some(self.blocks_count()),
+            blocks_centers: Some(self.blocks_centers().clone()),
+            blocks_edges: Some(self.blocks_edges().clone()),
+        };
+        axis_json.ser_json(d, s);
+    }
+}
+
 impl Default for Axis {
     fn default() -> Axis {
         Axis::new()
@@ -43,14 +156,7 @@ impl Axis {
     /// assert_eq!(*axis.blocks_edges(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
     /// assert_eq!(*axis.blocks_centers(), vec![1.5, 2.5, 3.5, 4.5, 5.5, 6.5, 7.5, 8.5, 9.5]);
     pub fn new() -> Axis {
-        Axis {
-            start: 1.0,
-            end: 10.0,
-            step: Some(1.0),
-            blocks_count: 9,
-            blocks_centers: vec![1.5, 2.5, 3.5, 4.5, 5.5, 6.5, 7.5, 8.5, 9.5],
-            blocks_edges: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
-        }
+        Axis::generate_axis_on_edges(1, 10, Some(1)).unwrap()
     }
 
     /// Creates Axis based on vec of input edges
@@ -71,37 +177,13 @@ impl Axis {
     /// let vec = vec![1.0, 1.00255];
     /// let ax = Axis::from_vec_as_edges(&vec).unwrap();
     /// assert_eq!(*ax.blocks_edges(), vec![1.0, 1.003]);
-    /// assert_eq!(*ax.blocks_centers(), vec![1.002]);
+    /// assert_eq!(*ax.blocks_centers(), vec![1.0015]);
     /// ```
     pub fn from_vec_as_edges<T: Into<f64> + Copy>(orig_edges: &[T]) -> Result<Axis, AxisError> {
-        if orig_edges.len() < 2 {
-            return Err(AxisError::TooSmallVec)
-        }
-        let mut axis_edges: Vec<f64> = Vec::with_capacity(orig_edges.len());
-
-        let mut pr_value = f64::NEG_INFINITY;
-        for edge in orig_edges {
-            let now_value: f64 = ((*edge).into() * 1000.0).round() / 1000.0;
-            if now_value - pr_value <= 0.001 { // If not, than edges will be also round from 0.001 to 0
-                if now_value <= pr_value {
-                    return Err(AxisError::NotOrderedVec);
-                }
-                return Err(AxisError::MinimalStep);
-            }
-            axis_edges.push(now_value);
-            pr_value = now_value;
-        }
-
+        let axis_edges = Axis::fixed_from_vec(orig_edges)?;
         let axis_centers = Axis::centers_from_edges(&axis_edges, None);
 
-        Ok(Axis {
-            start: axis_edges[0],
-            end: axis_edges[axis_edges.len() - 1],
-            step: None,
-            blocks_count: axis_centers.len(),
-            blocks_centers: axis_centers,
-            blocks_edges: axis_edges,
-        })
+        Ok(Axis::from_fixed(None, axis_centers, axis_edges))
     }
 
     /// Creates Axis based on vec of input centers
@@ -125,40 +207,15 @@ impl Axis {
     /// assert_eq!(*ax.blocks_centers(), vec![1.0, 1.002]);
     /// ```
     pub fn from_vec_as_centers<T: Into<f64> + Copy>(orig_centers: &[T]) -> Result<Axis, AxisError> {
-        if orig_centers.len() < 2 {
-            return Err(AxisError::TooSmallVec)
-        }
-        let mut axis_centers: Vec<f64> = Vec::with_capacity(orig_centers.len());
-
-        let mut pr_value = f64::NEG_INFINITY;
-        for edge in orig_centers {
-            let now_value: f64 = ((*edge).into() * 1000.0).round() / 1000.0;
-            if now_value - pr_value <= 0.001 { // If not, than centers will be also round from 0.001 to 0
-                if now_value <= pr_value {
-                    return Err(AxisError::NotOrderedVec);
-                }
-                return Err(AxisError::MinimalStep);
-            }
-            axis_centers.push(now_value);
-            pr_value = now_value;
-        }
-
+        let axis_centers = Axis::fixed_from_vec(orig_centers)?;
         let axis_edges = Axis::edges_from_centers(&axis_centers, None);
 
-        Ok(Axis {
-            start: axis_edges[0],
-            end: axis_edges[axis_edges.len() - 1],
-            step: None,
-            blocks_count: axis_centers.len(),
-            blocks_centers: axis_centers,
-            blocks_edges: axis_edges,
-        })
+        Ok(Axis::from_fixed(None, axis_centers, axis_edges))
     }
 
     /// Function to create axis based on generated edges using start, end and step
     /// If step is None it will be replaced to 1.0, step must be always bigger than 0.001
     /// Start must be always smaller than end
-    /// Axis recommended to be between -10000 and 10000, because possible float related errors
     /// # Examples
     /// ```
     /// use grunt::types::Axis;
@@ -176,30 +233,15 @@ impl Axis {
     pub fn generate_axis_on_edges<T: Into<f64>>(start: T, end: T, step: Option<T>) -> Result<Axis, AxisError> {
         let (start, step, iter_count) = Axis::get_generation_info(start, end, step)?;
 
-        let mut axis_edges: Vec<f64> = Vec::with_capacity(iter_count);
-
-        let mut pr_value = start;
-        for _ in 0..iter_count {
-            axis_edges.push(pr_value);
-            pr_value = ((pr_value + step) * 1000.0).round() / 1000.0;
-        }
-
+        let axis_edges: Vec<i64> = (0..iter_count as i64).map(|now_id| start + now_id * step).collect();
         let axis_centers = Axis::centers_from_edges(&axis_edges, Some(step));
 
-        Ok(Axis {
-            start,
-            end: axis_edges[axis_edges.len() - 1],
-            step: Some(step),
-            blocks_count: axis_centers.len(),
-            blocks_centers: axis_centers,
-            blocks_edges: axis_edges,
-        })
+        Ok(Axis::from_fixed(Some(step), axis_centers, axis_edges))
     }
 
     /// Function to create axis based on generated block's centers using start, end and step
     /// If step is None it will be replaced to 1.0, step must be always bigger than 0.001
     /// Start must be always smaller than end
-    /// Axis recommended to be between -10000 and 10000, because possible float related errors
     /// # Examples
     /// ```
     /// use grunt::types::Axis;
@@ -217,49 +259,236 @@ impl Axis {
     pub fn generate_axis_on_centers<T: Into<f64>>(start: T, end: T, step: Option<T>) -> Result<Axis, AxisError> {
         let (start, step, iter_count) = Axis::get_generation_info(start, end, step)?;
 
-        let mut axis_centers: Vec<f64> = Vec::with_capacity(iter_count);
+        let axis_centers: Vec<i64> = (0..iter_count as i64).map(|now_id| start + now_id * step).collect();
+        let axis_edges = Axis::edges_from_centers(&axis_centers, Some(step));
+
+        Ok(Axis::from_fixed(Some(step), axis_centers, axis_edges))
+    }
+}
 
-        let mut pr_value = start;
-        for _ in 0..iter_count {
-            axis_centers.push(pr_value);
-            pr_value = ((pr_value + step) * 1000.0).round() / 1000.0;
+impl Axis {
+    /// Function to create axis with uniform core and geometrically growing blocks after it.
+    /// Edges from start to core_end are generated with first_step, then every next step is
+    /// growth times bigger, last edge is never bigger than end.
+    /// Every step is rounded to third decimal place, so every step must be at least 0.002
+    /// # Examples
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_geometric(0, 2, 1, 2.0, 20).unwrap();
+    /// assert_eq!(*ax.blocks_edges(), vec![0.0, 1.0, 2.0, 4.0, 8.0, 16.0]);
+    /// assert_eq!(ax.step(), None);
+    /// ```
+    pub fn generate_geometric<T: Into<f64>>(start: T, core_end: T, first_step: T, growth: f64, end: T) -> Result<Axis, AxisError> {
+        let (start, core_end, end) = (Axis::to_fixed(start)?, Axis::to_fixed(core_end)?, Axis::to_fixed(end)?);
+        let first_step: f64 = first_step.into();
+        let core_step = Axis::to_fixed(first_step)?;
+
+        if start >= end {
+            return Err(Axis::invalid_range(start, end))
+        }
+        if core_end < start || core_end > end {
+            let (range_start, range_end) = if core_end < start { (start, core_end) } else { (core_end, end) };
+            return Err(Axis::invalid_range(range_start, range_end))
+        }
+        if core_step < INPUT_UNIT {
+            return Err(AxisError::TooSmallStep { step: Axis::from_fixed_units(core_step) })
+        }
+        if !growth.is_finite() || growth < 1.0 {
+            return Err(AxisError::InvalidGrowth { growth })
         }
 
-        let axis_edges = Axis::edges_from_centers(&axis_centers, Some(step));
+        let mut edges: Vec<i64> = (0..=(core_end - start) / core_step).map(|now_id| start + now_id * core_step).collect();
 
-        Ok(Axis {
-            start: axis_edges[0],
-            end: axis_edges[axis_edges.len() - 1],
-            step: Some(step),
-            blocks_count: axis_centers.len(),
-            blocks_centers: axis_centers,
-            blocks_edges: axis_edges,
-        })
+        let mut now_step = first_step;
+        loop {
+            now_step *= growth;
+            let last_edge = edges[edges.len() - 1];
+            // Step is compared before conversion, so huge steps can't overflow fixed-point units
+            if now_step > Axis::from_fixed_units(end - last_edge) {
+                break
+            }
+            match Axis::to_fixed(now_step).map(|now_step| last_edge + now_step) {
+                Ok(next_edge) if next_edge <= end => edges.push(next_edge),
+                _ => break,
+            }
+        }
+
+        if edges.len() < 2 {
+            return Err(AxisError::NotEnoughElements)
+        }
+        Axis::check_fixed_vec(&edges)?;
+
+        let centers = Axis::centers_from_edges(&edges, None);
+        Ok(Axis::from_fixed(None, centers, edges))
+    }
+
+    /// Function to create axis with blocks_count blocks which edges are evenly spaced in
+    /// logarithmic scale from start to end, start must be bigger than 0.
+    /// Edges are rounded to third decimal place, so neighbouring edges must differ at least by 0.002
+    /// # Examples
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_logarithmic(1, 1000, 3).unwrap();
+    /// assert_eq!(*ax.blocks_edges(), vec![1.0, 10.0, 100.0, 1000.0]);
+    /// ```
+    pub fn generate_logarithmic<T: Into<f64>>(start: T, end: T, blocks_count: usize) -> Result<Axis, AxisError> {
+        let (start, end): (f64, f64) = (start.into(), end.into());
+        let (fixed_start, fixed_end) = (Axis::to_fixed(start)?, Axis::to_fixed(end)?);
+
+        if fixed_start <= 0 {
+            return Err(AxisError::NotPositiveStart { start })
+        }
+        if fixed_start >= fixed_end {
+            return Err(Axis::invalid_range(fixed_start, fixed_end))
+        }
+        if blocks_count == 0 {
+            return Err(AxisError::NotEnoughElements)
+        }
+
+        let ratio = end / start;
+        let mut edges: Vec<i64> = (0..blocks_count).map(|now_id| {
+            Axis::to_fixed(start * ratio.powf(now_id as f64 / blocks_count as f64))
+        }).collect::<Result<_, _>>()?;
+        edges.push(fixed_end);
+
+        Axis::check_fixed_vec(&edges)?;
+        let centers = Axis::centers_from_edges(&edges, None);
+
+        Ok(Axis::from_fixed(None, centers, edges))
     }
 }
 
 impl Axis {
-    // Function to convert input data for axis generation to f64/usize.
-    fn get_generation_info<T: Into<f64>>(start: T, end: T, step: Option<T>) -> Result<(f64, f64, usize), AxisError> {
-        let start: f64 = (start.into() * 1000.0).round() / 1000.0;
-        let end: f64 = (end.into() * 1000.0).round() / 1000.0;
+    /// Converts value to fixed-point units, value is rounded to AXIS_DECIMALS decimal places.
+    /// Returns error if value isn't finite or doesn't fit into fixed-point units
+    /// # Example
+    /// ```
+    /// use grunt::types::{Axis, FIXED_SCALE};
+    /// assert_eq!(Axis::to_fixed(1.00255), Ok(10030));
+    /// assert_eq!(Axis::to_fixed(-2.5), Ok(-5 * FIXED_SCALE / 2));
+    /// assert!(Axis::to_fixed(1e15).is_err());
+    /// ```
+    pub fn to_fixed<T: Into<f64>>(value: T) -> Result<i64, AxisError> {
+        let value = value.into();
+        if !value.is_finite() || value.abs() > MAX_VALUE {
+            return Err(AxisError::OutOfRange { value })
+        }
 
-        if start >= end {
-            return Err(AxisError::InvalidRange)
+        ((value * 10_f64.powi(AXIS_DECIMALS as i32)).round() as i64)
+            .checked_mul(INPUT_UNIT)
+            .ok_or(AxisError::OutOfRange { value })
+    }
+
+    /// Converts fixed-point units to f64
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// assert_eq!(Axis::from_fixed_units(10005), 1.0005);
+    /// ```
+    pub fn from_fixed_units(units: i64) -> f64 {
+        units as f64 / FIXED_SCALE as f64
+    }
+
+    // Function to create axis from exact values
+    fn from_fixed(step: Option<i64>, centers: Vec<i64>, edges: Vec<i64>) -> Axis {
+        Axis { step, centers, edges, blocks_centers: OnceLock::new(), blocks_edges: OnceLock::new() }
+    }
+
+    // Function to convert coord of query to fixed-point units, coords out of fixed-point range are
+    // moved to its bounds, so they are out of any axis
+    fn query_fixed<T: Into<f64>>(coord: T) -> i64 {
+        let coord: f64 = coord.into();
+        Axis::to_fixed(coord).unwrap_or(if coord < 0.0 { -MAX_UNITS - 1 } else { MAX_UNITS + 1 })
+    }
+
+    // Function to rebuild axis from serialized edges without rounding, returns None if any value
+    // isn't exact value of fixed-point units, edges are invalid or step doesn't match them
+    fn from_stored_edges(orig_edges: &[f64], step: Option<f64>) -> Option<Axis> {
+        let edges: Vec<i64> = orig_edges.iter().map(|edge| Axis::exact_fixed(*edge)).collect::<Option<_>>()?;
+        if edges.len() < 2 || Axis::check_fixed_vec(&edges).is_err() {
+            return None
+        }
+
+        let step = match step {
+            Some(step) => {
+                let step = Axis::exact_fixed(step)?;
+                if !edges.windows(2).all(|pair| pair[1] - pair[0] == step) {
+                    return None
+                }
+                Some(step)
+            },
+            None => None,
+        };
+
+        let centers = Axis::centers_from_edges(&edges, step);
+        Some(Axis::from_fixed(step, centers, edges))
+    }
+
+    // Function to convert value to fixed-point units only if it is exact value of them
+    fn exact_fixed(value: f64) -> Option<i64> {
+        if !value.is_finite() || value.abs() > MAX_VALUE {
+            return None
         }
 
-        let step: f64 = if let Some(step) = step {
-            let temp_step = (step.into() * 1000.0).round() / 1000.0;
-            if temp_step >= 0.001 {
-                temp_step
-            } else {
-                return Err(AxisError::TooSmallStep);
+        let units = (value * FIXED_SCALE as f64).round() as i64;
+        (Axis::from_fixed_units(units) == value).then_some(units)
+    }
+
+    // Function to convert input vec to fixed-point units, checks order and minimal step
+    fn fixed_from_vec<T: Into<f64> + Copy>(orig_values: &[T]) -> Result<Vec<i64>, AxisError> {
+        if orig_values.len() < 2 {
+            return Err(AxisError::TooSmallVec { len: orig_values.len() })
+        }
+        let values: Vec<i64> = orig_values.iter().map(|value| Axis::to_fixed(*value)).collect::<Result<_, _>>()?;
+        Axis::check_fixed_vec(&values)?;
+
+        Ok(values)
+    }
+
+    // Function to check that fixed-point values increase at least by 0.002
+    fn check_fixed_vec(values: &[i64]) -> Result<(), AxisError> {
+        for (index, pair) in values.windows(2).enumerate() {
+            Axis::check_fixed_pair(index + 1, pair[0], pair[1])?;
+        }
+
+        Ok(())
+    }
+
+    // Function to check that value at index increases previous one at least by 0.002
+    fn check_fixed_pair(index: usize, pr_value: i64, value: i64) -> Result<(), AxisError> {
+        if value - pr_value <= INPUT_UNIT { // If not, middle values will be rounded to neighbours
+            let (prev, value_f64) = (Axis::from_fixed_units(pr_value), Axis::from_fixed_units(value));
+            if value <= pr_value {
+                return Err(AxisError::NotOrderedVec { index, prev, value: value_f64 });
             }
-        } else {
-            1.0
+            return Err(AxisError::MinimalStep { index, prev, value: value_f64 });
+        }
+
+        Ok(())
+    }
+
+    fn invalid_range(start: i64, end: i64) -> AxisError {
+        AxisError::InvalidRange { start: Axis::from_fixed_units(start), end: Axis::from_fixed_units(end) }
+    }
+
+    // Function to convert input data for axis generation to fixed-point units and number of values
+    fn get_generation_info<T: Into<f64>>(start: T, end: T, step: Option<T>) -> Result<(i64, i64, usize), AxisError> {
+        let start = Axis::to_fixed(start)?;
+        let end = Axis::to_fixed(end)?;
+
+        if start >= end {
+            return Err(Axis::invalid_range(start, end))
+        }
+
+        let step = match step {
+            Some(step) => Axis::to_fixed(step)?,
+            None => FIXED_SCALE,
         };
+        if step < INPUT_UNIT {
+            return Err(AxisError::TooSmallStep { step: Axis::from_fixed_units(step) });
+        }
 
-        let iter_count = (((end-start) * 1000.0).round() / 1000.0 / step).floor() as usize + 1;
+        let iter_count = ((end - start) / step) as usize + 1;
 
         if iter_count < 2 {
             return Err(AxisError::NotEnoughElements)
@@ -268,49 +497,41 @@ impl Axis {
         Ok((start, step, iter_count))
     }
 
+    // Function to find middle between two fixed-point values, halves of unit are rounded away from zero
+    fn middle(first: i64, second: i64) -> i64 {
+        let sum = first + second;
+        (sum.abs() + 1) / 2 * sum.signum()
+    }
+
     // Function to generate centers based on block's edges, required more than 2 values inside vec
-    fn centers_from_edges(axis_edges: &[f64], step: Option<f64>) -> Vec<f64> {
-        let mut axis_centers: Vec<f64> = Vec::with_capacity(axis_edges.len() - 1);
+    // Centers are exact if edges have input precision
+    fn centers_from_edges(axis_edges: &[i64], step: Option<i64>) -> Vec<i64> {
+        match step {
+            Some(step) => axis_edges[..axis_edges.len() - 1].iter().map(|edge| edge + step / 2).collect(),
+            None => axis_edges.windows(2).map(|edges| Axis::middle(edges[0], edges[1])).collect(),
+        }
+    }
 
+    // Function to generate edges based on block's centers, required more than 2 values inside vec
+    // Edges are exact if centers have input precision
+    fn edges_from_centers(axis_centers: &[i64], step: Option<i64>) -> Vec<i64> {
         if let Some(step) = step {
-            let mut pr_value = axis_edges[0] + step / 2.0;
-            for _ in 0..(axis_edges.len() - 1) {
-                axis_centers.push(pr_value);
-                pr_value = ((pr_value + step) * 1000.0).round() / 1000.0;
-            }
-        } else {
-            let mut pr_value = axis_edges[0];
-            for now_elem in &axis_edges[1..] {
-                axis_centers.push(((pr_value + now_elem) * 500.0).round() / 1000.0);
-                pr_value = *now_elem;
-            }
+            let first_edge = axis_centers[0] - step / 2;
+            return (0..=axis_centers.len() as i64).map(|now_id| first_edge + now_id * step).collect()
         }
 
-        axis_centers
-    }
+        let mut axis_edges: Vec<i64> = Vec::with_capacity(axis_centers.len() + 1);
 
-    // Function to generate edges based on block's centers, required more than 2 values inside vec
-    fn edges_from_centers(axis_centers: &[f64], step: Option<f64>) -> Vec<f64> {
-        let mut axis_edges: Vec<f64> = Vec::with_capacity(axis_centers.len() + 1);
+        let mut pr_value = axis_centers[0];
+        let mut last_diff = 0;
 
-        if let Some(step) = step {
-            let mut pr_value = axis_centers[0] - step / 2.0;
-            for _ in 0..(axis_centers.len() + 1) {
-                axis_edges.push(pr_value);
-                pr_value = ((pr_value + step) * 1000.0).round() / 1000.0;
-            }
-        } else {
-            let mut pr_value = axis_centers[0];
-            let mut last_diff = 0.0;
-
-            axis_edges.push(((pr_value - (axis_centers[1] - pr_value) / 2.0) * 1000.0).round() / 1000.0);
-            for now_elem in &axis_centers[1..] {
-                last_diff = (now_elem - pr_value) / 2.0;
-                axis_edges.push(((pr_value + last_diff) * 1000.0).round() / 1000.0);
-                pr_value = *now_elem;
-            }
-            axis_edges.push(((pr_value + last_diff) * 1000.0).round() / 1000.0);
+        axis_edges.push(Axis::middle(pr_value, pr_value - (axis_centers[1] - pr_value)));
+        for now_elem in &axis_centers[1..] {
+            last_diff = now_elem - pr_value;
+            axis_edges.push(Axis::middle(pr_value, *now_elem));
+            pr_value = *now_elem;
         }
+        axis_edges.push(Axis::middle(pr_value, pr_value + last_diff));
 
         axis_edges
     }
@@ -326,8 +547,8 @@ impl Axis {
     /// assert_eq!(ax.start(), 0.75);
     /// ```
     pub fn start(&self) -> f64 {
-        self.start
-    } 
+        Axis::from_fixed_units(self.start_fixed())
+    }
 
     /// Returns last edge for axis
     /// # Example
@@ -338,7 +559,7 @@ impl Axis {
     /// assert_eq!(ax.end(), 2.25);
     /// ```
     pub fn end(&self) -> f64 {
-        self.end
+        Axis::from_fixed_units(self.end_fixed())
     }
 
     /// Returns step if axis was generated with it, otherwise - None
@@ -350,7 +571,7 @@ impl Axis {
     /// assert_eq!(ax.step(), Some(0.5));
     /// ```
     pub fn step(&self) -> Option<f64> {
-        self.step
+        self.step.map(Axis::from_fixed_units)
     }
 
     /// Returns number of blocks inside axis, block - "object" that is between two edges 
@@ -361,10 +582,11 @@ impl Axis {
     /// assert_eq!(ax.blocks_count(), 2);
     /// ```
     pub fn blocks_count(&self) -> usize {
-        self.blocks_count
+        self.centers.len()
     }
 
-    /// Returns blocks centers, where block is "object" that is between two edges
+    /// Returns blocks centers, where block is "object" that is between two edges.
+    /// Values are computed from fixed-point units on first call
     /// # Example
     /// ```
     /// use grunt::types::Axis;
@@ -372,10 +594,11 @@ impl Axis {
     /// assert_eq!(*ax.blocks_centers(), vec![1.25, 1.75]);
     /// ```
     pub fn blocks_centers(&self) -> &Vec<f64> {
-        &self.blocks_centers
+        self.blocks_centers.get_or_init(|| self.centers.iter().map(|center| Axis::from_fixed_units(*center)).collect())
     }
 
-    /// Returns axises edges, edges represents borders, which are separating two block
+    /// Returns axises edges, edges represents borders, which are separating two block.
+    /// Values are computed from fixed-point units on first call
     /// # Example
     /// ```
     /// use grunt::types::Axis;
@@ -383,6 +606,266 @@ impl Axis {
     /// assert_eq!(*ax.blocks_edges(), vec![0.75, 1.25, 1.75, 2.25]);
     /// ```
     pub fn blocks_edges(&self) -> &Vec<f64> {
-        &self.blocks_edges
+        self.blocks_edges.get_or_init(|| self.edges.iter().map(|edge| Axis::from_fixed_units(*edge)).collect())
+    }
+
+    /// Returns center of block with block_id, panics if block_id is out of axis
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(1.0, 2.0, Some(0.5)).unwrap();
+    /// assert_eq!(ax.center(1), 1.75);
+    /// ```
+    pub fn center(&self, block_id: usize) -> f64 {
+        Axis::from_fixed_units(self.centers[block_id])
+    }
+
+    /// Returns edge with edge_id, panics if edge_id is out of axis
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(1.0, 2.0, Some(0.5)).unwrap();
+    /// assert_eq!(ax.edge(2), 2.0);
+    /// ```
+    pub fn edge(&self, edge_id: usize) -> f64 {
+        Axis::from_fixed_units(self.edges[edge_id])
+    }
+}
+
+impl Axis {
+    /// Returns exact first edge in fixed-point units
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_centers(1.0, 2.0, Some(0.5)).unwrap();
+    /// assert_eq!(ax.start_fixed(), 7500);
+    /// ```
+    pub fn start_fixed(&self) -> i64 {
+        self.edges[0]
+    }
+
+    /// Returns exact last edge in fixed-point units
+    pub fn end_fixed(&self) -> i64 {
+        self.edges[self.edges.len() - 1]
+    }
+
+    /// Returns exact step in fixed-point units if axis was generated with it
+    pub fn step_fixed(&self) -> Option<i64> {
+        self.step
+    }
+
+    /// Returns exact blocks centers in fixed-point units
+    pub fn centers_fixed(&self) -> &[i64] {
+        &self.centers
+    }
+
+    /// Returns exact edges in fixed-point units
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(1.0, 1.003, Some(0.001)).unwrap();
+    /// assert_eq!(ax.edges_fixed(), &[10000, 10010, 10020, 10030]);
+    /// assert_eq!(ax.centers_fixed(), &[10005, 10015, 10025]);
+    /// ```
+    pub fn edges_fixed(&self) -> &[i64] {
+        &self.edges
+    }
+}
+
+impl Axis {
+    /// Returns index of block which contains coord, last edge belongs to last block.
+    /// Coord is rounded to AXIS_DECIMALS decimal places. Block is found in O(1) for axis with step
+    /// and with binary search otherwise
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::from_vec_as_edges(&[0.0, 1.0, 5.0, 6.0]).unwrap();
+    /// assert_eq!(ax.locate(3.2), Some(1));
+    /// assert_eq!(ax.locate(6.0), Some(2));
+    /// assert_eq!(ax.locate(-0.1), None);
+    /// ```
+    pub fn locate<T: Into<f64>>(&self, coord: T) -> Option<usize> {
+        let coord = Axis::query_fixed(coord);
+        if coord < self.start_fixed() || coord > self.end_fixed() {
+            return None
+        }
+
+        let block_id = match self.step {
+            Some(step) => ((coord - self.start_fixed()) / step) as usize,
+            None => self.edges.partition_point(|edge| *edge <= coord) - 1,
+        };
+
+        Some(block_id.min(self.blocks_count() - 1))
+    }
+
+    /// Returns two neighbouring blocks and their weights for linear interpolation between
+    /// centers, weights sum is 1. Before first center and after last one the nearest block gets
+    /// full weight. Returns None if coord is out of axis
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(0, 4, Some(1)).unwrap();
+    /// assert_eq!(ax.interp_weights(1.75), Some([(1, 0.75), (2, 0.25)]));
+    /// assert_eq!(ax.interp_weights(0.2), Some([(0, 1.0), (0, 0.0)]));
+    /// ```
+    pub fn interp_weights<T: Into<f64>>(&self, coord: T) -> Option<[(usize, f64); 2]> {
+        self.interp_weights_fixed(Axis::query_fixed(coord))
+    }
+
+    // Function to find interpolation weights of coord in fixed-point units
+    fn interp_weights_fixed(&self, coord: i64) -> Option<[(usize, f64); 2]> {
+        if coord < self.start_fixed() || coord > self.end_fixed() {
+            return None
+        }
+
+        let next_id = self.centers.partition_point(|center| *center <= coord);
+        if next_id == 0 {
+            return Some([(0, 1.0), (0, 0.0)])
+        }
+        if next_id == self.blocks_count() {
+            return Some([(next_id - 1, 1.0), (next_id - 1, 0.0)])
+        }
+
+        let pr_center = self.centers[next_id - 1];
+        let next_weight = (coord - pr_center) as f64 / (self.centers[next_id] - pr_center) as f64;
+
+        Some([(next_id - 1, 1.0 - next_weight), (next_id, next_weight)])
+    }
+
+    /// Returns range of blocks which centers are between min and max (both included)
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(0, 10, Some(1)).unwrap();
+    /// assert_eq!(ax.blocks_in(2.0, 5.5), 2..6);
+    /// assert!(ax.blocks_in(20.0, 30.0).is_empty());
+    /// ```
+    pub fn blocks_in<T: Into<f64>>(&self, min: T, max: T) -> std::ops::Range<usize> {
+        let min = Axis::query_fixed(min);
+        let max = Axis::query_fixed(max);
+
+        let first_id = self.centers.partition_point(|center| *center < min);
+        let last_id = self.centers.partition_point(|center| *center <= max);
+
+        first_id..last_id.max(first_id)
+    }
+}
+
+impl Axis {
+    /// Splits every block into factor blocks, new edges are rounded to AXIS_DECIMALS decimal
+    /// places. Step is kept if it can be divided exactly. Returns error on the first new block
+    /// which is smaller than minimal step
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(0, 2, Some(1)).unwrap().refine(4).unwrap();
+    /// assert_eq!(*ax.blocks_edges(), vec![0.0, 0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0]);
+    /// assert_eq!(ax.step(), Some(0.25));
+    /// ```
+    pub fn refine(&self, factor: usize) -> Result<Axis, AxisError> {
+        if factor == 0 {
+            return Err(AxisError::InvalidFactor)
+        }
+        let divisible_step = i64::try_from(factor).ok()
+            .and_then(|factor| factor.checked_mul(INPUT_UNIT))
+            .and_then(|factor_units| self.step.filter(|step| step % factor_units == 0));
+
+        if let Some(step) = divisible_step {
+            let step = step / factor as i64;
+            if step <= INPUT_UNIT {
+                let (prev, value) = (Axis::from_fixed_units(self.start_fixed()), Axis::from_fixed_units(self.start_fixed() + step));
+                return Err(AxisError::MinimalStep { index: 1, prev, value })
+            }
+            let edges: Vec<i64> = (0..=(self.blocks_count() * factor) as i64).map(|now_id| self.start_fixed() + now_id * step).collect();
+            let centers = Axis::centers_from_edges(&edges, Some(step));
+            return Ok(Axis::from_fixed(Some(step), centers, edges))
+        }
+
+        // Parts are computed in i128, so big blocks and factors can't overflow. Last part of every
+        // block ends exactly at its edge, so refined blocks always cover original ones
+        let factor_units = factor as i128 * INPUT_UNIT as i128;
+        let mut edges: Vec<i64> = vec![self.start_fixed()];
+        for pair in self.edges.windows(2) {
+            let block_size = (pair[1] - pair[0]) as i128;
+            for now_part in 1..=factor as i128 {
+                let now_edge = if now_part == factor as i128 {
+                    pair[1]
+                } else {
+                    pair[0] + ((block_size * now_part + factor_units / 2) / factor_units * INPUT_UNIT as i128) as i64
+                };
+                Axis::check_fixed_pair(edges.len(), edges[edges.len() - 1], now_edge)?;
+                edges.push(now_edge);
+            }
+        }
+
+        let centers = Axis::centers_from_edges(&edges, None);
+
+        Ok(Axis::from_fixed(None, centers, edges))
+    }
+
+    /// Merges every factor blocks into one, last block contains remaining blocks if blocks count
+    /// isn't divisible by factor. Step is kept if all new blocks have the same size
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(0, 5, Some(1)).unwrap().coarsen(2).unwrap();
+    /// assert_eq!(*ax.blocks_edges(), vec![0.0, 2.0, 4.0, 5.0]);
+    /// assert_eq!(ax.step(), None);
+    /// ```
+    pub fn coarsen(&self, factor: usize) -> Result<Axis, AxisError> {
+        if factor == 0 {
+            return Err(AxisError::InvalidFactor)
+        }
+
+        let mut edges: Vec<i64> = self.edges.iter().step_by(factor).copied().collect();
+        if !self.blocks_count().is_multiple_of(factor) {
+            edges.push(self.end_fixed());
+        }
+
+        match self.step.filter(|_| self.blocks_count().is_multiple_of(factor)) {
+            Some(step) => {
+                let step = step * factor as i64;
+                let centers = Axis::centers_from_edges(&edges, Some(step));
+                Ok(Axis::from_fixed(Some(step), centers, edges))
+            },
+            None => {
+                let centers = Axis::centers_from_edges(&edges, None);
+                Ok(Axis::from_fixed(None, centers, edges))
+            },
+        }
+    }
+
+    /// Returns interpolation weights (see interp_weights) of every center of new_axis in this
+    /// axis, centers out of this axis are moved to the nearest edge
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::generate_axis_on_edges(0, 4, Some(1)).unwrap();
+    /// let new_ax = Axis::generate_axis_on_edges(0, 4, Some(2)).unwrap();
+    /// assert_eq!(ax.resample(&new_ax), vec![[(0, 0.5), (1, 0.5)], [(2, 0.5), (3, 0.5)]]);
+    /// ```
+    pub fn resample(&self, new_axis: &Axis) -> Vec<[(usize, f64); 2]> {
+        new_axis.centers.iter().map(|center| {
+            self.interp_weights_fixed((*center).clamp(self.start_fixed(), self.end_fixed())).unwrap()
+        }).collect()
+    }
+
+    /// Returns axis with blocks from range, values of blocks and step are kept
+    /// # Example
+    /// ```
+    /// use grunt::types::Axis;
+    /// let ax = Axis::from_vec_as_edges(&[0, 1, 3, 6]).unwrap().slice(1..3).unwrap();
+    /// assert_eq!(*ax.blocks_edges(), vec![1.0, 3.0, 6.0]);
+    /// assert_eq!(*ax.blocks_centers(), vec![2.0, 4.5]);
+    /// ```
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Result<Axis, AxisError> {
+        if range.is_empty() || range.end > self.blocks_count() {
+            return Err(AxisError::InvalidBlocksRange { start: range.start, end: range.end, blocks_count: self.blocks_count() })
+        }
+
+        let centers = self.centers[range.clone()].to_vec();
+        let edges = self.edges[range.start..=range.end].to_vec();
+
+        Ok(Axis::from_fixed(self.step, centers, edges))
     }
 }
@@ -1,29 +1,57 @@
-use crate::types::{BordersParams, BorderType};
+use std::str::Chars;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState};
+
+use crate::types::json::BordersParamsJson;
+use crate::types::{BordersParams, BorderType, BordersOrder};
 
 // Possible errors during Borders creation
 #[derive(Debug, Clone)]
 pub enum BorderError {
     IncorrectBordersCount,
     IncorrectBordersLimits,
+    UnorderableLimits,
+    IncorrectStepProbability,
+    IncorrectNoiseParams,
+    IncorrectFieldParams,
 }
 
 impl std::fmt::Display for BorderError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             BorderError::IncorrectBordersCount => write!(f, "Number of borders must be between 1 and 255"),
-            BorderError::IncorrectBordersLimits => write!(f, "Your border limits must be [min value, <= max value]")
+            BorderError::IncorrectBordersLimits => write!(f, "Your border limits must be [min value, <= max value]"),
+            BorderError::UnorderableLimits => write!(f, "Max limit of every border must be at least max limit of \
+                previous border plus min thickness"),
+            BorderError::IncorrectStepProbability => write!(f, "Probability of step must be between 0 and 1"),
+            BorderError::IncorrectNoiseParams => write!(f, "Noise must have at least 1 octave, positive persistence \
+                and wavelength"),
+            BorderError::IncorrectFieldParams => write!(f, "Gaussian field must have positive range, non-negative \
+                sill and nugget"),
         }
     }
 }
 
 impl std::error::Error for BorderError {}
 
+/// BordersParams are deserialized using BordersParams::new and with_order, borders_order is optional
+impl DeJson for BordersParams {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<BordersParams, DeJsonErr> {
+        let borders_json = BordersParamsJson::de_json(state, input)?;
+
+        BordersParams::new(borders_json.number_of_borders, &borders_json.borders_type, &borders_json.borders_limits)
+            .and_then(|borders| borders.with_order(borders_json.borders_order.unwrap_or(BordersOrder::Free)))
+            .map_err(|err| state.err_parse(format!("BordersParams: {err}").as_str()))
+    }
+}
+
 impl Default for BordersParams {
     fn default() -> BordersParams {
         BordersParams {
             number_of_borders: 2,
             borders_type: vec![BorderType::Random, BorderType::Random],
-            borders_limits: vec![[5, 10], [15, 20]]
+            borders_limits: vec![[5, 10], [15, 20]],
+            borders_order: BordersOrder::Free,
         }
     }
 }
@@ -33,30 +61,75 @@ impl BordersParams {
         number_of_borders: T,
         borders_type: &[BorderType],
         borders_limits:&[[u32; 2]]
-    ) -> Result<BordersParams, Box<dyn std::error::Error>>
+    ) -> Result<BordersParams, BorderError>
     where 
         T: TryInto<u8>,
     {
         let number_of_borders = match number_of_borders.try_into() {
             Ok(value) => {
                 if value == 0 {
-                    return Err(Box::new(BorderError::IncorrectBordersCount))
+                    return Err(BorderError::IncorrectBordersCount)
                 }
                 value
             },
-            Err(_) => return Err(Box::new(BorderError::IncorrectBordersCount))
+            Err(_) => return Err(BorderError::IncorrectBordersCount)
         };
 
         if !borders_limits.iter().all(|now_limits| now_limits[0] <= now_limits[1]) {
-            return Err(Box::new(BorderError::IncorrectBordersLimits))
+            return Err(BorderError::IncorrectBordersLimits)
+        }
+
+        for border_type in borders_type {
+            match border_type {
+                BorderType::RandomWithStep(_step, prob) => {
+                    if !(0.0..=1.0).contains(prob) {
+                        return Err(BorderError::IncorrectStepProbability)
+                    }
+                },
+                BorderType::Noise { octaves, persistence, wavelength } => {
+                    let is_positive = |value: &f32| value.is_finite() && *value > 0.0;
+                    if *octaves == 0 || !is_positive(persistence) || !is_positive(wavelength) {
+                        return Err(BorderError::IncorrectNoiseParams)
+                    }
+                },
+                BorderType::GaussianField { model: _, range, sill, nugget } => {
+                    let is_non_negative = |value: &f32| value.is_finite() && *value >= 0.0;
+                    if !(is_non_negative(range) && *range > 0.0 && is_non_negative(sill) && is_non_negative(nugget)) {
+                        return Err(BorderError::IncorrectFieldParams)
+                    }
+                },
+                BorderType::Random => (),
+            }
         }
 
         Ok(BordersParams {
             number_of_borders,
             borders_type: borders_type.to_vec(),
             borders_limits: borders_limits.to_vec(),
+            borders_order: BordersOrder::Free,
         })
     }
+
+    /// Sets order of borders. Ordered borders are generated one by one and every border is lifted
+    /// down to previous border plus min thickness, so limits must allow every border to be placed
+    /// below previous one
+    pub fn with_order(mut self, borders_order: BordersOrder) -> Result<BordersParams, BorderError> {
+        if let BordersOrder::Ordered(min_thickness) = borders_order {
+            let limits_count = self.borders_limits.len();
+            for now_border_id in 1..self.number_of_borders as usize {
+                let pr_max_limit = self.borders_limits[(now_border_id - 1) % limits_count][1];
+                let now_max_limit = self.borders_limits[now_border_id % limits_count][1];
+
+                match pr_max_limit.checked_add(min_thickness) {
+                    Some(lowest_max_limit) if lowest_max_limit <= now_max_limit => (),
+                    _ => return Err(BorderError::UnorderableLimits),
+                }
+            }
+        }
+
+        self.borders_order = borders_order;
+        Ok(self)
+    }
 }
 
 impl BordersParams {
@@ -71,4 +144,8 @@ impl BordersParams {
     pub fn borders_limits(&self) -> &Vec<[u32; 2]> {
         &self.borders_limits
     }
+
+    pub fn borders_order(&self) -> BordersOrder {
+        self.borders_order
+    }
 }
@@ -0,0 +1,103 @@
+use std::str::Chars;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState};
+
+use crate::types::json::FaultJson;
+use crate::types::Fault;
+
+// Possible errors during Fault creation
+#[derive(Debug, Clone)]
+pub enum FaultError {
+    IncorrectStrike,
+    IncorrectDip,
+    IncorrectPosition,
+}
+
+impl std::fmt::Display for FaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FaultError::IncorrectStrike => write!(f, "Strike must be between 0 and 360 degrees"),
+            FaultError::IncorrectDip => write!(f, "Dip must be bigger than 0 and not bigger than 90 degrees"),
+            FaultError::IncorrectPosition => write!(f, "Fault position must be finite"),
+        }
+    }
+}
+
+impl std::error::Error for FaultError {}
+
+/// Fault is deserialized using Fault::new
+impl DeJson for Fault {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Fault, DeJsonErr> {
+        let fault_json = FaultJson::de_json(state, input)?;
+
+        Fault::new(fault_json.strike, fault_json.dip, fault_json.x, fault_json.y, fault_json.throw)
+            .map_err(|err| state.err_parse(format!("Fault: {err}").as_str()))
+    }
+}
+
+impl Fault {
+    /// Creates fault plane which crosses top of model at point (x, y)
+    /// strike - azimuth of fault trace in degrees, measured clockwise from Y axis
+    /// dip - angle between fault plane and horizontal plane in degrees, plane dips to the right of strike
+    /// throw - vertical displacement of hanging wall in borders units, positive moves it down
+    pub fn new(strike: f64, dip: f64, x: f64, y: f64, throw: i32) -> Result<Fault, FaultError> {
+        if !(0.0..=360.0).contains(&strike) {
+            return Err(FaultError::IncorrectStrike)
+        }
+
+        if !(dip > 0.0 && dip <= 90.0) {
+            return Err(FaultError::IncorrectDip)
+        }
+
+        if !x.is_finite() || !y.is_finite() {
+            return Err(FaultError::IncorrectPosition)
+        }
+
+        Ok(Fault {
+            strike,
+            dip,
+            x,
+            y,
+            throw,
+        })
+    }
+}
+
+impl Fault {
+    pub fn strike(&self) -> f64 {
+        self.strike
+    }
+
+    pub fn dip(&self) -> f64 {
+        self.dip
+    }
+
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub fn throw(&self) -> i32 {
+        self.throw
+    }
+
+    /// Returns horizontal distance from fault trace to point (x, y) in dip direction
+    pub fn trace_distance(&self, x: f64, y: f64) -> f64 {
+        let (strike_sin, strike_cos) = self.strike.to_radians().sin_cos();
+        // Dip direction is strike rotated by 90 degrees clockwise
+        (x - self.x) * strike_cos - (y - self.y) * strike_sin
+    }
+
+    /// Returns true if point at depth (from top of model) is above fault plane, so it is on hanging wall
+    pub fn is_hanging_wall(&self, x: f64, y: f64, depth: f64) -> bool {
+        let distance = self.trace_distance(x, y);
+        if self.dip >= 90.0 {
+            return distance > 0.0
+        }
+
+        depth < distance * self.dip.to_radians().tan()
+    }
+}
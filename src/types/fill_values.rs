@@ -1,3 +1,8 @@
+use std::str::Chars;
+
+use nanoserde::{DeJson, DeJsonErr, DeJsonState};
+
+use crate::types::json::FillValuesJson;
 use crate::types::{FillValues, FillType};
 
 // Possible errors during FillValues creation
@@ -18,6 +23,17 @@ impl std::fmt::Display for FillValuesError {
 
 impl std::error::Error for FillValuesError {}
 
+/// FillValues are deserialized using FillValues::new, values_smooth (0 by default) and
+/// is_preset_ordered (true by default) are optional
+impl DeJson for FillValues {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<FillValues, DeJsonErr> {
+        let fill_json = FillValuesJson::de_json(state, input)?;
+
+        FillValues::new(fill_json.fill_values, fill_json.values_smooth, fill_json.is_preset_ordered.unwrap_or(true))
+            .map_err(|err| state.err_parse(format!("FillValues: {err}").as_str()))
+    }
+}
+
 impl Default for FillValues {
     fn default() -> FillValues {
         FillValues {
@@ -32,6 +48,10 @@ impl FillValues {
     pub fn new (fill_values: Vec<FillType>, values_smooth: u16, is_preset_ordered: bool) -> 
         Result<FillValues, FillValuesError> 
     {
+        if fill_values.is_empty() {
+            return Err(FillValuesError::NotEnoughtElements)
+        }
+
         for fill_value in &fill_values {
             match fill_value {
                 FillType::RandomBetween(lower_bound, upper_bound) => {
@@ -0,0 +1,54 @@
+use nanoserde::{DeJson, SerJson};
+
+use crate::types::{Axis, BorderType, BordersOrder, BordersParams, FillType, FillValues, Fault};
+
+// Axis as it is stored in json, every field is optional to allow short axis descriptions
+#[derive(DeJson, SerJson)]
+pub struct AxisJson {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub step: Option<f64>,
+    pub blocks_count: Option<usize>,
+    pub blocks_centers: Option<Vec<f64>>,
+    pub blocks_edges: Option<Vec<f64>>,
+}
+
+// BordersParams as they are stored in json
+#[derive(DeJson)]
+pub struct BordersParamsJson {
+    pub number_of_borders: u64,
+    pub borders_type: Vec<BorderType>,
+    pub borders_limits: Vec<[u32; 2]>,
+    pub borders_order: Option<BordersOrder>,
+}
+
+// FillValues as they are stored in json
+#[derive(DeJson)]
+pub struct FillValuesJson {
+    pub fill_values: Vec<FillType>,
+    #[nserde(default)]
+    pub values_smooth: u16,
+    pub is_preset_ordered: Option<bool>,
+}
+
+// Fault as it is stored in json
+#[derive(DeJson)]
+pub struct FaultJson {
+    pub strike: f64,
+    pub dip: f64,
+    pub x: f64,
+    pub y: f64,
+    pub throw: i32,
+}
+
+// Params3D as they are stored in json
+#[derive(DeJson)]
+pub struct Params3DJson {
+    pub axis_x: Axis,
+    pub axis_y: Axis,
+    pub axis_z: Option<Axis>,
+    pub borders_params: BordersParams,
+    pub fill_values: Vec<FillValues>,
+    #[nserde(default)]
+    pub faults: Vec<Fault>,
+}
@@ -1,6 +1,29 @@
 use std::sync::Arc;
+use std::path::Path;
+use std::str::Chars;
 
-use crate::types::{Params3D, Axis, BordersParams, FillValues};
+use nanoserde::{DeJson, DeJsonErr, DeJsonState};
+
+use crate::error::GruntError;
+use crate::types::json::Params3DJson;
+use crate::types::{Params3D, Axis, BordersParams, FillValues, Fault};
+
+/// Params3D are deserialized from the same json as they are serialized, every part is validated
+/// by its constructors. axis_z and faults are optional
+impl DeJson for Params3D {
+    fn de_json(state: &mut DeJsonState, input: &mut Chars) -> Result<Params3D, DeJsonErr> {
+        let params_json = Params3DJson::de_json(state, input)?;
+
+        Ok(Params3D {
+            axis_x: Arc::new(params_json.axis_x),
+            axis_y: Arc::new(params_json.axis_y),
+            axis_z: params_json.axis_z.map(Arc::new),
+            borders_params: Arc::new(params_json.borders_params),
+            fill_values: Arc::new(params_json.fill_values),
+            faults: Arc::new(params_json.faults),
+        })
+    }
+}
 
 impl Default for Params3D {
     fn default() -> Params3D {
@@ -10,8 +33,10 @@ impl Default for Params3D {
         Params3D {
             axis_x: axis_r.clone(),
             axis_y: axis_r,
+            axis_z: None,
             borders_params: borders,
             fill_values,
+            faults: Arc::new(Vec::new()),
         }
     }
 }
@@ -20,6 +45,7 @@ impl Params3D {
     pub fn new(
         axis_x: Arc<Axis>,
         axis_y: Arc<Axis>,
+        axis_z: Option<Arc<Axis>>,
         borders: Arc<BordersParams>,
         fill_values: Arc<Vec<FillValues>>
     ) -> Arc<Params3D> {
@@ -27,8 +53,34 @@ impl Params3D {
         Arc::new(Params3D {
             axis_x,
             axis_y,
+            axis_z,
             borders_params: borders,
-            fill_values
+            fill_values,
+            faults: Arc::new(Vec::new()),
+        })
+    }
+
+    /// Loads params from json config file, config has the same structure as params exported with
+    /// model, so exported params can be used again
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Arc<Params3D>, GruntError> {
+        let config = std::fs::read_to_string(path)?;
+        Ok(Arc::new(Params3D::deserialize_json(&config)?))
+    }
+
+    /// Returns copy of params with new horizontal axes
+    pub fn with_axes(&self, axis_x: Arc<Axis>, axis_y: Arc<Axis>) -> Arc<Params3D> {
+        Arc::new(Params3D {
+            axis_x,
+            axis_y,
+            ..self.clone()
+        })
+    }
+
+    /// Returns copy of params with faults, faults are applied to borders one by one
+    pub fn with_faults(&self, faults: Arc<Vec<Fault>>) -> Arc<Params3D> {
+        Arc::new(Params3D {
+            faults,
+            ..self.clone()
         })
     }
 }
@@ -42,6 +94,11 @@ impl Params3D {
         self.axis_y.clone()
     }
 
+    /// Returns depth axis if exists, borders values are indexes of its edges
+    pub fn axis_z(&self) -> Option<Arc<Axis>> {
+        self.axis_z.clone()
+    }
+
     pub fn borders(&self) -> Arc<BordersParams> {
         self.borders_params.clone()
     }
@@ -49,4 +106,8 @@ impl Params3D {
     pub fn fill_values(&self) -> Arc<Vec<FillValues>> {
         self.fill_values.clone()
     }
+
+    pub fn faults(&self) -> Arc<Vec<Fault>> {
+        self.faults.clone()
+    }
 }